@@ -1,6 +1,27 @@
+mod use_channel;
+mod use_emit;
 mod use_listen;
+mod use_listen_buffered;
+mod use_mutation;
 mod use_invoke;
+mod use_invoke_bytes;
+mod use_invoke_infinite;
+mod use_invoke_resource;
+mod use_invoke_watch;
+mod use_query;
+mod use_typed_command;
+mod utils;
 pub mod plugins;
 
+pub use use_channel::*;
+pub use use_emit::*;
 pub use use_listen::*;
+pub use use_listen_buffered::*;
+pub use use_mutation::*;
 pub use use_invoke::*;
+pub use use_invoke_bytes::*;
+pub use use_invoke_infinite::*;
+pub use use_invoke_resource::*;
+pub use use_invoke_watch::*;
+pub use use_query::*;
+pub use use_typed_command::*;
@@ -8,7 +8,7 @@ use reactive_graph::{
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
-use crate::{UseTauriError, UseTauriWithReturn, use_invoke_with_args, use_invoke_with_options};
+use crate::{UseTauriError, UseTauriWithReturn, use_invoke_with_args};
 
 pub fn use_ask_dialog<T>() -> UseDialogReturn<ConfirmDialogOpions, T>
 where
@@ -18,9 +18,13 @@ where
     let (dialog_options, set_dialog_options) = signal(None::<(ConfirmDialogOpions, T)>);
 
     let UseTauriWithReturn {
-        data,
         error,
+        pending,
+        started_at,
+        finished_at,
         trigger,
+        callbacks,
+        ..
     } = use_invoke_with_args::<ConfirmDialogOpions, bool>("plugin:dialog|ask");
 
     Effect::new(move || {
@@ -29,24 +33,22 @@ where
         }
     });
 
-    Effect::new(move || {
-        if let Some(b) = data.get() {
-            if b {
-                set_transfer.set(Some(dialog_options.get_untracked().unwrap().1));
-            }
-            set_dialog_options.update_untracked(|v| *v = None);
+    callbacks.on_success(move |confirmed| {
+        if *confirmed && let Some((_, transfer)) = dialog_options.get_untracked() {
+            set_transfer.set(Some(transfer));
         }
     });
 
-    Effect::new(move || {
-        if let Some(_) = error.get() {
-            set_dialog_options.update_untracked(|v| *v = None);
-        }
+    callbacks.on_settled(move |_| {
+        set_dialog_options.update_untracked(|v| *v = None);
     });
 
     UseDialogReturn {
         transfer: transfer.into(),
-        error: error.into(),
+        error,
+        pending,
+        started_at,
+        finished_at,
         set_dialog_options,
     }
 }
@@ -59,9 +61,13 @@ where
     let (dialog_options, set_dialog_options) = signal(None::<(ConfirmDialogOpions, T)>);
 
     let UseTauriWithReturn {
-        data,
         error,
+        pending,
+        started_at,
+        finished_at,
         trigger,
+        callbacks,
+        ..
     } = use_invoke_with_args::<ConfirmDialogOpions, bool>("plugin:dialog|confirm");
 
     Effect::new(move || {
@@ -70,24 +76,22 @@ where
         }
     });
 
-    Effect::new(move || {
-        if let Some(b) = data.get() {
-            if b {
-                set_transfer.set(Some(dialog_options.get_untracked().unwrap().1));
-            }
-            set_dialog_options.update_untracked(|v| *v = None);
+    callbacks.on_success(move |confirmed| {
+        if *confirmed && let Some((_, transfer)) = dialog_options.get_untracked() {
+            set_transfer.set(Some(transfer));
         }
     });
 
-    Effect::new(move || {
-        if let Some(_) = error.get() {
-            set_dialog_options.update_untracked(|v| *v = None);
-        }
+    callbacks.on_settled(move |_| {
+        set_dialog_options.update_untracked(|v| *v = None);
     });
 
     UseDialogReturn {
         transfer: transfer.into(),
-        error: error.into(),
+        error,
+        pending,
+        started_at,
+        finished_at,
         set_dialog_options,
     }
 }
@@ -100,9 +104,13 @@ where
     let (dialog_options, set_dialog_options) = signal(None::<(MessageDialogOpions, T)>);
 
     let UseTauriWithReturn {
-        data,
         error,
+        pending,
+        started_at,
+        finished_at,
         trigger,
+        callbacks,
+        ..
     } = use_invoke_with_args::<MessageDialogOpions, ()>("plugin:dialog|message");
 
     Effect::new(move || {
@@ -111,22 +119,22 @@ where
         }
     });
 
-    Effect::new(move || {
-        if let Some(()) = data.get() {
-            set_transfer.set(Some(dialog_options.get_untracked().unwrap().1));
-            set_dialog_options.update_untracked(|v| *v = None);
+    callbacks.on_success(move |()| {
+        if let Some((_, transfer)) = dialog_options.get_untracked() {
+            set_transfer.set(Some(transfer));
         }
     });
 
-    Effect::new(move || {
-        if let Some(_) = error.get() {
-            set_dialog_options.update_untracked(|v| *v = None);
-        }
+    callbacks.on_settled(move |_| {
+        set_dialog_options.update_untracked(|v| *v = None);
     });
 
     UseDialogReturn {
         transfer: transfer.into(),
-        error: error.into(),
+        error,
+        pending,
+        started_at,
+        finished_at,
         set_dialog_options,
     }
 }
@@ -139,11 +147,8 @@ pub fn use_open_dialog() -> UseTauriWithReturn<OpenDialogOptions, Option<OpenDia
 
     let (args_wrapper, set_args_wrapper) = signal(None::<OpenDialogOptions>);
 
-    let UseTauriWithReturn {
-        data,
-        error,
-        trigger,
-    } = use_invoke_with_args::<OptionsWrapper, Option<OpenDialogReturn>>("plugin:dialog|open");
+    let ret = use_invoke_with_args::<OptionsWrapper, Option<OpenDialogReturn>>("plugin:dialog|open");
+    let trigger = ret.trigger;
 
     Effect::new(move || {
         if let Some(options) = args_wrapper.get() {
@@ -152,15 +157,28 @@ pub fn use_open_dialog() -> UseTauriWithReturn<OpenDialogOptions, Option<OpenDia
         }
     });
 
-    UseTauriWithReturn {
-        data: data.into(),
-        error: error.into(),
-        trigger: set_args_wrapper,
-    }
+    ret.with_trigger(set_args_wrapper, |options| OptionsWrapper { options }, |wrapper| wrapper.options.clone())
 }
 
 pub fn use_save_dialog() -> UseTauriWithReturn<SaveDialogOptions, Option<PathBuf>> {
-    use_invoke_with_options::<SaveDialogOptions, Option<PathBuf>>("plugin:dialog|save")
+    #[derive(Clone, Serialize)]
+    struct OptionsWrapper {
+        options: SaveDialogOptions,
+    }
+
+    let (args_wrapper, set_args_wrapper) = signal(None::<SaveDialogOptions>);
+
+    let ret = use_invoke_with_args::<OptionsWrapper, Option<PathBuf>>("plugin:dialog|save");
+    let trigger = ret.trigger;
+
+    Effect::new(move || {
+        if let Some(options) = args_wrapper.get() {
+            trigger.set(Some(OptionsWrapper { options }));
+            set_args_wrapper.update_untracked(|v| *v = None);
+        }
+    });
+
+    ret.with_trigger(set_args_wrapper, |options| OptionsWrapper { options }, |wrapper| wrapper.options.clone())
 }
 
 pub struct UseDialogReturn<O, T>
@@ -170,6 +188,10 @@ where
 {
     pub transfer: Signal<Option<T>>,
     pub error: Signal<Option<UseTauriError>, LocalStorage>,
+    /// `true` while the dialog is open
+    pub pending: Signal<bool>,
+    pub started_at: Signal<Option<f64>>,
+    pub finished_at: Signal<Option<f64>>,
     pub set_dialog_options: WriteSignal<Option<(O, T)>>,
 }
 
@@ -1,7 +1,12 @@
 use std::path::PathBuf;
+use reactive_graph::{
+    effect::Effect,
+    signal::signal,
+    traits::{Get as _, Set as _, UpdateUntracked as _},
+};
 use serde::Serialize;
 
-use crate::{use_invoke_with_args, use_invoke_with_options, UseTauriWithReturn};
+use crate::{use_invoke_with_args_and_config, UseInvokeConfig, UseTauriWithReturn};
 
 #[derive(Clone, Serialize)]
 #[serde(rename = "camelCase")]
@@ -10,9 +15,34 @@ pub struct ExistsOptions {
 }
 
 pub fn use_exists() -> UseTauriWithReturn<ExistsOptions, bool> {
-    use_invoke_with_options::<ExistsOptions, bool>("plugin:fs|exists")
+    use_exists_with_config(UseInvokeConfig::default())
+}
+
+pub fn use_exists_with_config(config: UseInvokeConfig<bool>) -> UseTauriWithReturn<ExistsOptions, bool> {
+    #[derive(Clone, Serialize)]
+    struct OptionsWrapper {
+        options: ExistsOptions,
+    }
+
+    let (args_wrapper, set_args_wrapper) = signal(None::<ExistsOptions>);
+
+    let ret = use_invoke_with_args_and_config::<OptionsWrapper, bool>("plugin:fs|exists", config);
+    let trigger = ret.trigger;
+
+    Effect::new(move || {
+        if let Some(options) = args_wrapper.get() {
+            trigger.set(Some(OptionsWrapper { options }));
+            set_args_wrapper.update_untracked(|v| *v = None);
+        }
+    });
+
+    ret.with_trigger(set_args_wrapper, |options| OptionsWrapper { options }, |wrapper| wrapper.options.clone())
 }
 
 pub fn use_size() -> UseTauriWithReturn<PathBuf, u64> {
-    use_invoke_with_args::<PathBuf, u64>("plugin:fs|size")
+    use_size_with_config(UseInvokeConfig::default())
+}
+
+pub fn use_size_with_config(config: UseInvokeConfig<u64>) -> UseTauriWithReturn<PathBuf, u64> {
+    use_invoke_with_args_and_config::<PathBuf, u64>("plugin:fs|size", config)
 }
@@ -0,0 +1,173 @@
+use std::marker::PhantomData;
+use reactive_graph::{
+    owner::{on_cleanup, LocalStorage, StoredValue},
+    signal::{signal, signal_local},
+    traits::{Set as _, Update as _, WithValue as _},
+    wrappers::read::Signal,
+};
+use serde::{de::DeserializeOwned, Serialize, Serializer};
+use wasm_bindgen::prelude::*;
+
+use crate::UseTauriError;
+
+/// Creates a Tauri `Channel` for commands streaming their response through `tauri::ipc::Channel`.
+///
+/// The core `Channel` buffers messages arriving out of order and hands them over by index,
+/// so `message` and `messages` always follow the order the backend sent them in.
+///
+/// ```rust,ignore
+/// #[derive(Clone, Serialize)]
+/// struct DownloadArgs {
+///     url: String,
+///     on_progress: Channel<Progress>,
+/// }
+///
+/// #[component]
+/// pub fn Demo() -> impl IntoView {
+///     let UseChannelReturn { channel, message: progress, .. } = use_channel::<Progress>();
+///     let UseTauriWithReturn { trigger: download, .. } = use_invoke_with_args::<DownloadArgs, ()>("download");
+///
+///     view!{
+///         ...
+///         <button on:click=move |_| download.set(Some(DownloadArgs { url: url(), on_progress: channel }))>download</button>
+///         ...
+///     }
+/// }
+/// ```
+pub fn use_channel<T>() -> UseChannelReturn<T>
+where
+    T: DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    use_channel_with_config(UseChannelConfig::default())
+}
+
+/// `use_channel` with extra configuration, e.g. keeping every received message
+pub fn use_channel_with_config<T>(config: UseChannelConfig) -> UseChannelReturn<T>
+where
+    T: DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    let UseChannelConfig { accumulate } = config;
+
+    let (message, set_message) = signal(None::<T>);
+    let (messages, set_messages) = signal(Vec::<T>::new());
+    let (index, set_index) = signal(None::<u32>);
+    let (error, set_error) = signal_local(None::<UseTauriError>);
+
+    let on_msg = Closure::wrap(Box::new(move |msg: JsValue| {
+        // counts messages failing to deserialize too, to stay in step with the backend's index
+        set_index.update(|i| *i = Some(i.map_or(0, |i| i + 1)));
+        match serde_wasm_bindgen::from_value::<T>(msg) {
+            Ok(msg) => {
+                if accumulate {
+                    set_messages.update(|v| v.push(msg.clone()));
+                }
+                set_message.set(Some(msg));
+            }
+            Err(err) => set_error.set(Some(UseTauriError::Deserialize(err.to_string()))),
+        }
+    }) as Box<dyn Fn(JsValue)>);
+
+    let js_channel = JsChannel::new();
+    js_channel.set_onmessage(on_msg.as_ref().unchecked_ref());
+    let id = js_channel.id();
+
+    let inner = StoredValue::new_local((js_channel, on_msg));
+
+    on_cleanup(move || {
+        // the backend may still hold the channel, so detach the callback before it is dropped
+        inner.with_value(|(js_channel, _)| {
+            js_channel.set_onmessage(&js_sys::Function::new_no_args(""));
+        });
+    });
+
+    UseChannelReturn {
+        channel: Channel {
+            id,
+            _marker: PhantomData,
+        },
+        message: message.into(),
+        messages: messages.into(),
+        index: index.into(),
+        error: error.into(),
+    }
+}
+
+/// Options for [`use_channel_with_config`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UseChannelConfig {
+    /// Keep every received message in `messages`
+    pub accumulate: bool,
+}
+
+impl UseChannelConfig {
+    pub fn accumulate(mut self, accumulate: bool) -> Self {
+        self.accumulate = accumulate;
+        self
+    }
+}
+
+/// Handle of a frontend channel, to be embedded in the args of a command
+/// taking a `tauri::ipc::Channel<T>`.
+pub struct Channel<T> {
+    id: u32,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Channel<T> {
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+}
+
+impl<T> Clone for Channel<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Channel<T> {}
+
+impl<T> std::fmt::Debug for Channel<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Channel").field("id", &self.id).finish()
+    }
+}
+
+impl<T> Serialize for Channel<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        // same representation as `Channel.toJSON()` in `@tauri-apps/api/core`
+        serializer.serialize_str(&format!("__CHANNEL__:{}", self.id))
+    }
+}
+
+pub struct UseChannelReturn<T>
+where
+    T: DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    pub channel: Channel<T>,
+    /// The latest received message
+    pub message: Signal<Option<T>>,
+    /// Every received message, only filled with [`UseChannelConfig::accumulate`]
+    pub messages: Signal<Vec<T>>,
+    /// Index of the latest received message, including one that failed to deserialize
+    pub index: Signal<Option<u32>>,
+    pub error: Signal<Option<UseTauriError>, LocalStorage>,
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"], js_name = Channel)]
+    type JsChannel;
+
+    #[wasm_bindgen(constructor, js_namespace = ["window", "__TAURI__", "core"], js_class = "Channel")]
+    fn new() -> JsChannel;
+
+    #[wasm_bindgen(method, getter)]
+    fn id(this: &JsChannel) -> u32;
+
+    #[wasm_bindgen(method, setter = onmessage)]
+    fn set_onmessage(this: &JsChannel, handler: &js_sys::Function);
+}
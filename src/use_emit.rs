@@ -0,0 +1,147 @@
+use reactive_graph::{
+    effect::Effect,
+    owner::LocalStorage,
+    signal::{signal, signal_local, WriteSignal},
+    spawn_local_scoped,
+    traits::{Get as _, Set as _, Update as _, UpdateUntracked as _},
+    wrappers::read::Signal,
+};
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::{utils::rejection_message, EventTarget, EventType, UseListenError};
+
+/// Emits an event to every listener, without any reactive state.
+///
+/// ```rust,ignore
+/// let on_save = move |_| {
+///     spawn_local(async move {
+///         if let Err(err) = emit(EventType::custom("app://demo_saved"), demo).await {
+///             ...
+///         }
+///     });
+/// };
+/// ```
+pub async fn emit<T>(event: EventType, payload: T) -> Result<(), UseListenError>
+where
+    T: Serialize,
+{
+    let event = event.name()?;
+    let payload = to_payload(payload)?;
+
+    emit_js(&event, payload)
+        .await
+        .map(|_| ())
+        .map_err(|err| UseListenError::Event(event, rejection_message(err)))
+}
+
+/// `emit` only reaching the listeners of `target`
+pub async fn emit_to<T>(target: EventTarget, event: EventType, payload: T) -> Result<(), UseListenError>
+where
+    T: Serialize,
+{
+    let event = event.name()?;
+    let target = serde_wasm_bindgen::to_value(&target)
+        .map_err(|err| UseListenError::Serialize(err.to_string()))?;
+    let payload = to_payload(payload)?;
+
+    emit_to_js(target, &event, payload)
+        .await
+        .map(|_| ())
+        .map_err(|err| UseListenError::Event(event, rejection_message(err)))
+}
+
+/// Emits `event` with the payload set on the trigger
+///
+/// ```rust,ignore
+/// #[component]
+/// pub fn Demo() -> impl IntoView {
+///     let UseEmitReturn {
+///         trigger: notify,
+///         ..
+///     } = use_emit::<DemoStatus>(EventType::custom("app://demo_status"));
+///
+///     view!{
+///         ...
+///         <button on:click=move |_| notify.set(Some(DemoStatus::Done))>done</button>
+///         ...
+///     }
+/// }
+/// ```
+pub fn use_emit<T>(event: EventType) -> UseEmitReturn<T>
+where
+    T: Serialize + Clone + Send + Sync + 'static,
+{
+    use_emit_inner(event, None)
+}
+
+/// `use_emit` only reaching the listeners of `target`
+pub fn use_emit_with_target<T>(event: EventType, target: EventTarget) -> UseEmitReturn<T>
+where
+    T: Serialize + Clone + Send + Sync + 'static,
+{
+    use_emit_inner(event, Some(target))
+}
+
+fn use_emit_inner<T>(event: EventType, target: Option<EventTarget>) -> UseEmitReturn<T>
+where
+    T: Serialize + Clone + Send + Sync + 'static,
+{
+    let (error, set_error) = signal_local(None::<UseListenError>);
+    let (in_flight, set_in_flight) = signal(0usize);
+    let (trigger, set_trigger) = signal(None::<T>);
+
+    Effect::new(move || {
+        if let Some(payload) = trigger.get() {
+            let event = event.clone();
+            set_in_flight.update(|n| *n += 1);
+
+            spawn_local_scoped(async move {
+                let result = match target {
+                    Some(target) => emit_to(target, event, payload).await,
+                    None => emit(event, payload).await,
+                };
+                set_error.set(result.err());
+                set_in_flight.update(|n| *n -= 1);
+            });
+
+            set_trigger.update_untracked(|v| *v = None);
+        }
+    });
+
+    UseEmitReturn {
+        error: error.into(),
+        pending: Signal::derive(move || in_flight.get() > 0),
+        trigger: set_trigger,
+    }
+}
+
+/// Payloads go through `JSON.stringify`, so maps must be plain objects
+fn to_payload<T>(payload: T) -> Result<JsValue, UseListenError>
+where
+    T: Serialize,
+{
+    let serializer = serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true);
+    payload
+        .serialize(&serializer)
+        .map_err(|err| UseListenError::Serialize(err.to_string()))
+}
+
+pub struct UseEmitReturn<T>
+where
+    T: Serialize + Clone + Send + Sync + 'static,
+{
+    pub error: Signal<Option<UseListenError>, LocalStorage>,
+    /// `true` while an emit is in flight
+    pub pending: Signal<bool>,
+    pub trigger: WriteSignal<Option<T>>,
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(catch, js_namespace = ["window", "__TAURI__", "event"], js_name = emit)]
+    async fn emit_js(event: &str, payload: JsValue) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch, js_namespace = ["window", "__TAURI__", "event"], js_name = emitTo)]
+    async fn emit_to_js(target: JsValue, event: &str, payload: JsValue) -> Result<JsValue, JsValue>;
+}
@@ -1,18 +1,21 @@
 use core::fmt;
+use std::{collections::{BTreeMap, VecDeque}, pin::Pin, sync::Arc, time::Duration};
 
 use reactive_graph::{
     effect::Effect, 
     owner::{LocalStorage, StoredValue}, 
     signal::{signal, signal_local, WriteSignal}, 
     spawn_local_scoped, 
-    traits::{Get as _, GetValue as _, Set as _, UpdateUntracked as _}, 
+    traits::{Get as _, GetUntracked as _, GetValue as _, Set as _, SetValue as _, Update as _, UpdateUntracked as _, UpdateValue as _}, 
     wrappers::read::Signal
 };
 use wasm_bindgen::prelude::*;
 
+use crate::utils::{self, rate_limited, sleep};
+
 /// An alias function of `use_invoke` without options or parameters 
 /// 
-/// ```rust
+/// ```rust,ignore
 /// #[component]
 /// pub fn Demo() -> impl IntoView {
 ///     let UseTauriWithReturn { 
@@ -32,16 +35,24 @@ use wasm_bindgen::prelude::*;
 pub fn use_command<T>(
     cmd: &'static str,
 ) -> UseTauriWithReturn<(), T> 
+where 
+    T: serde::de::DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    use_command_with_config(cmd, UseInvokeConfig::default())
+}
+
+/// `use_command` with the configuration of [`use_invoke_with_config`]
+pub fn use_command_with_config<T>(
+    cmd: &'static str,
+    config: UseInvokeConfig<T>,
+) -> UseTauriWithReturn<(), T> 
 where 
     T: serde::de::DeserializeOwned + Clone + Send + Sync + 'static,
 {
     let (args, set_args) = signal(None::<()>);
 
-    let UseTauriReturn { 
-        data, 
-        error, 
-        trigger 
-    } = use_invoke::<(), (), T>(cmd);
+    let ret = use_invoke_with_config::<(), (), T>(cmd, config);
+    let trigger = ret.trigger;
 
     Effect::new(move || {
         if let Some(()) = args.get() {
@@ -50,16 +61,12 @@ where
         }
     });
 
-    UseTauriWithReturn { 
-        data: data.into(), 
-        error: error.into(), 
-        trigger: set_args
-    }
+    ret.with_trigger(set_args, |()| ((), ()), |_| ())
 }
 
 /// An alias function of `use_invoke` without options 
 /// 
-/// ```rust
+/// ```rust,ignore
 /// #[component]
 /// pub fn Demo() -> impl IntoView {
 ///     let UseTauriWithReturn { 
@@ -79,17 +86,33 @@ where
 pub fn use_invoke_with_args<Args, T>(
     cmd: &'static str,
 ) -> UseTauriWithReturn<Args, T> 
+where 
+    Args: serde::Serialize + Clone + Send + Sync + 'static,
+    T: serde::de::DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    use_invoke_with_args_and_config(cmd, UseInvokeConfig::default())
+}
+
+/// `use_invoke_with_args` with the configuration of [`use_invoke_with_config`]
+/// 
+/// ```rust,ignore
+/// let UseTauriWithReturn { data: results, trigger: search, .. } = use_invoke_with_args_and_config::<SearchQuery, Vec<Demo>>(
+///     "search",
+///     UseInvokeConfig::default().debounce(Duration::from_millis(300)),
+/// );
+/// ```
+pub fn use_invoke_with_args_and_config<Args, T>(
+    cmd: &'static str,
+    config: UseInvokeConfig<T>,
+) -> UseTauriWithReturn<Args, T> 
 where 
     Args: serde::Serialize + Clone + Send + Sync + 'static,
     T: serde::de::DeserializeOwned + Clone + Send + Sync + 'static,
 {
     let (args, set_args) = signal(None::<Args>);
 
-    let UseTauriReturn { 
-        data, 
-        error, 
-        trigger 
-    } = use_invoke::<Args, (), T>(cmd);
+    let ret = use_invoke_with_config::<Args, (), T>(cmd, config);
+    let trigger = ret.trigger;
 
     Effect::new(move || {
         if let Some(args) = args.get() {
@@ -98,27 +121,25 @@ where
         }
     });
 
-    UseTauriWithReturn { 
-        data: data.into(), 
-        error: error.into(), 
-        trigger: set_args
-    }
+    ret.with_trigger(set_args, |args| (args, ()), |(args, ())| args.clone())
 }
 
 /// An alias function of `use_invoke` without parameters 
 /// 
-/// ```rust
+/// The options are Tauri's invoke options, usually [`InvokeOptions`].
+/// 
+/// ```rust,ignore
 /// #[component]
 /// pub fn Demo() -> impl IntoView {
 ///     let UseTauriWithReturn { 
 ///         data: demo, 
 ///         trigger: config,
 ///         ..
-///     } = use_invoke_with_options::<Options, ()>("config_data");;
+///     } = use_invoke_with_options::<InvokeOptions, ()>("config_data");;
 ///     
 ///     view!{
 ///         ...
-///         <button on:click=move |_| config.set(Some(Options::new(...)))>config data</button>
+///         <button on:click=move |_| config.set(Some(InvokeOptions::new().header("x-trace-id", id)))>config data</button>
 ///         ...
 ///     }
 /// }
@@ -127,17 +148,26 @@ where
 pub fn use_invoke_with_options<Opts, T>(
     cmd: &'static str,
 ) -> UseTauriWithReturn<Opts, T> 
+where 
+    Opts: serde::Serialize + Clone + Send + Sync + 'static,
+    T: serde::de::DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    use_invoke_with_options_and_config(cmd, UseInvokeConfig::default())
+}
+
+/// `use_invoke_with_options` with the configuration of [`use_invoke_with_config`]
+pub fn use_invoke_with_options_and_config<Opts, T>(
+    cmd: &'static str,
+    config: UseInvokeConfig<T>,
+) -> UseTauriWithReturn<Opts, T> 
 where 
     Opts: serde::Serialize + Clone + Send + Sync + 'static,
     T: serde::de::DeserializeOwned + Clone + Send + Sync + 'static,
 {
     let (opts, set_opts) = signal(None::<Opts>);
 
-    let UseTauriReturn { 
-        data, 
-        error, 
-        trigger 
-    } = use_invoke::<(), Opts, T>(cmd);
+    let ret = use_invoke_with_config::<(), Opts, T>(cmd, config);
+    let trigger = ret.trigger;
 
     Effect::new(move || {
         if let Some(opts) = opts.get() {
@@ -146,11 +176,7 @@ where
         }
     });
 
-    UseTauriWithReturn { 
-        data: data.into(), 
-        error: error.into(), 
-        trigger: set_opts
-    }
+    ret.with_trigger(set_opts, |opts| ((), opts), |((), opts)| opts.clone())
 }
 
 /// A `leptos` wrapper for Tauri's native `invoke` function.
@@ -162,105 +188,946 @@ where
     Opts: serde::Serialize + Clone + Send + Sync + 'static,
     T: serde::de::DeserializeOwned + Clone + Send + Sync + 'static,
 {
+    use_invoke_with_config(cmd, UseInvokeConfig::default())
+}
+
+/// `use_invoke` with extra configuration, e.g. how overlapping calls are handled
+/// 
+/// ```rust,ignore
+/// let UseTauriReturn { data, trigger, .. } = use_invoke_with_config::<Query, (), Vec<Demo>>(
+///     "search_data",
+///     UseInvokeConfig::default()
+///         .policy(InvokePolicy::Queue)
+///         .on_error(|err| toast(err.to_string())),
+/// );
+/// ```
+pub fn use_invoke_with_config<Args, Opts, T>(
+    cmd: &'static str,
+    config: UseInvokeConfig<T>,
+) -> UseTauriReturn<Args, Opts, T> 
+where 
+    Args: serde::Serialize + Clone + Send + Sync + 'static,
+    Opts: serde::Serialize + Clone + Send + Sync + 'static,
+    T: serde::de::DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    use_invoke_inner(cmd, config, invoke_typed::<Args, Opts, T>)
+}
+
+pub(crate) fn use_invoke_inner<Args, Opts, T, F, Fut>(
+    cmd: &'static str,
+    config: UseInvokeConfig<T>,
+    callback: F,
+) -> UseTauriReturn<Args, Opts, T> 
+where 
+    Args: serde::Serialize + Clone + Send + Sync + 'static,
+    Opts: serde::Serialize + Clone + Send + Sync + 'static,
+    T: serde::de::DeserializeOwned + Clone + Send + Sync + 'static,
+    F: Fn(&'static str, Args, Opts) -> Fut + Copy + 'static,
+    Fut: Future<Output = Result<T, UseTauriError>> + 'static,
+{
+    let UseInvokeConfig { policy, retry, rate_limit, timeout, history: history_size, on_success, on_error, on_settled } = config;
+    let retry = StoredValue::new(retry);
+
     let (data, set_data) = signal(None::<T>);
     let (error, set_error) = signal_local(None::<UseTauriError>);
+    let (in_flight, set_in_flight) = signal(0usize);
+    let (started_at, set_started_at) = signal(None::<f64>);
+    let (finished_at, set_finished_at) = signal(None::<f64>);
+    let (attempt, set_attempt) = signal(0u32);
+    let (previous, set_previous) = signal(None::<T>);
+    let (history, set_history) = signal(VecDeque::<InvokeRecord<(Args, Opts), T>>::new());
     let (trigger, set_trigger) = signal(None::<(Args, Opts)>);
 
-    let init = StoredValue::new(move |cmd: &'static str, args: Args, options: Opts| {
-        #[derive(Clone, serde::Serialize)]
-        struct OptionsWrapper<Opts>
-        where 
-            Opts: serde::Serialize + Clone + 'static,
-        {
-            options: Opts
-        }
+    // id of the most recently started call and of the most recently applied result
+    let generation = StoredValue::new(0u64);
+    let applied = StoredValue::new(0u64);
+    // bumped by every reset, discarding the calls dispatched before it, started or not
+    let resets = StoredValue::new(0u64);
+    let callbacks = InvokeCallbacks::new(on_success, on_error, on_settled);
+    let queue = StoredValue::new(VecDeque::<(Args, Opts)>::new());
+    let draining = StoredValue::new(false);
 
-        let args = args.clone();
-        let options = OptionsWrapper{
-            options: options.clone()
-        };
+    // expects the in-flight count to be incremented already, the call only starts once polled
+    let call = move |args: Args, options: Opts, timeout: Option<Duration>| {
+        let dispatched_after = resets.try_get_value();
 
-        spawn_local_scoped(async move {
-            let args = match serde_wasm_bindgen::to_value(&args) {
-                Ok(value) => value,
-                Err(err) =>  {
-                    set_error.set(Some(UseTauriError::Serialize(err.to_string())));
-                    return;
+        async move {
+            let _in_flight = InFlight(set_in_flight);
+            // the hook's owner may be cleaned up before or while the call runs,
+            // its results are then stale and only returned to the caller
+            let id = generation.try_get_value().unwrap_or_default() + 1;
+            generation.set_value(id);
+            set_started_at.set(Some(js_sys::Date::now()));
+
+            let is_stale = move || {
+                let (Some(generation), Some(applied), Some(resets)) = 
+                    (generation.try_get_value(), applied.try_get_value(), resets.try_get_value()) 
+                else {
+                    return true;
+                };
+                dispatched_after != Some(resets) || match policy {
+                    InvokePolicy::LatestWins => id != generation,
+                    _ => id < applied,
                 }
             };
 
-            let options = match serde_wasm_bindgen::to_value(&options) {
-                Ok(value) => value,
-                Err(err) =>  {
-                    set_error.set(Some(UseTauriError::Serialize(err.to_string())));
-                    return;
+            let retry = retry.try_get_value().flatten();
+            let mut current = 1;
+
+            let result = loop {
+                set_attempt.set(current);
+
+                let result = match timeout {
+                    Some(after) => utils::timeout(after, callback(cmd, args.clone(), options.clone()))
+                        .await
+                        .unwrap_or(Err(UseTauriError::Timeout { cmd, after })),
+                    None => callback(cmd, args.clone(), options.clone()).await,
+                };
+                match (&result, &retry) {
+                    (Err(err), Some(retry)) if current < retry.max_attempts && (retry.retry_if)(err) && !is_stale() => {
+                        sleep(retry.backoff.delay(current)).await;
+                        current += 1;
+                    }
+                    _ => break result,
                 }
             };
 
-            match invoke(cmd, args, options).await {
-                Ok(data) => {
-                    match serde_wasm_bindgen::from_value::<T>(data) {
-                        Ok(data) => set_data.set(Some(data)),
-                        Err(err) => set_error.set(Some(UseTauriError::Deserialize(err.to_string())))
+            if !is_stale() {
+                applied.set_value(id);
+                match &result {
+                    Ok(value) => {
+                        if history_size > 0 {
+                            set_previous.set(data.get_untracked());
+                        }
+                        set_data.set(Some(value.clone()));
+
+                        if history_size > 0 {
+                            set_history.update(|history| {
+                                history.push_back(InvokeRecord {
+                                    args: (args, options),
+                                    data: value.clone(),
+                                    at: js_sys::Date::now(),
+                                });
+                                while history.len() > history_size {
+                                    history.pop_front();
+                                }
+                            });
+                        }
                     }
+                    Err(err) => set_error.set(Some(err.clone())),
                 }
-                Err(err) => {
-                    let err_str = err.as_string().unwrap_or_else(|| "Unknown error".to_string());
-                    set_error.set(Some(UseTauriError::Command(cmd, err_str)))
+                callbacks.run(&result);
+            }
+
+            set_finished_at.set(Some(js_sys::Date::now()));
+            result
+        }
+    };
+    
+    let (dispatch, cancel_dispatch) = rate_limited(rate_limit, move |(args, opts): (Args, Opts)| {
+        match policy {
+            InvokePolicy::DropWhilePending if in_flight.get_untracked() > 0 => {}
+            InvokePolicy::Queue => {
+                set_in_flight.update(|n| *n += 1);
+                queue.update_value(|q| q.push_back((args, opts)));
+
+                if !draining.get_value() {
+                    draining.set_value(true);
+                    spawn_local_scoped(async move {
+                        while let Some((args, opts)) = queue.try_update_value(|q| q.pop_front()).flatten() {
+                            let _ = call(args, opts, timeout).await;
+                        }
+                        draining.set_value(false);
+                    });
                 }
             }
-        });
+            _ => {
+                set_in_flight.update(|n| *n += 1);
+                let call = call(args, opts, timeout);
+                spawn_local_scoped(async move {
+                    let _ = call.await;
+                });
+            }
+        }
     });
-    
+
     Effect::new(move || {
-        let init = init.get_value();
-        if let Some((args, opts)) = trigger.get() {
-            init(cmd, args, opts);
+        if let Some(value) = trigger.get() {
+            dispatch(value);
         }
         set_trigger.update_untracked(|v| *v = None);
     });
 
+    let execute = send_wrapper::SendWrapper::new(move |args: Args, opts: Opts, timeout: Option<Duration>| {
+        let call = call(args, opts, timeout);
+        Box::pin(async move {
+            set_in_flight.update(|n| *n += 1);
+            call.await
+        }) as InvokeFuture<T>
+    });
+
+    let reset = move || {
+        resets.update_value(|n| *n += 1);
+        cancel_dispatch();
+        let queued = queue.try_update_value(|q| q.drain(..).count()).unwrap_or_default();
+        set_in_flight.update(|n| *n = n.saturating_sub(queued));
+        set_data.set(None);
+        set_previous.set(None);
+        set_history.update(|history| history.clear());
+        set_error.set(None);
+        set_started_at.set(None);
+        set_finished_at.set(None);
+        set_attempt.set(0);
+    };
+
     UseTauriReturn { 
         data: data.into(), 
         error: error.into(),
-        trigger: set_trigger
+        pending: Signal::derive(move || in_flight.get() > 0),
+        started_at: started_at.into(),
+        finished_at: finished_at.into(),
+        attempt: attempt.into(),
+        previous: previous.into(),
+        history: history.into(),
+        trigger: set_trigger,
+        execute: {
+            let execute = execute.clone();
+            Arc::new(move |args, opts| execute(args, opts, timeout))
+        },
+        execute_with_timeout: Arc::new(move |args, opts, after| execute(args, opts, Some(after))),
+        reset: Arc::new(reset),
+        clear_error: Arc::new(move || set_error.set(None)),
+        callbacks,
+    }
+}
+
+/// Decrements the in-flight count once a call settles, or once its future is dropped
+struct InFlight(WriteSignal<usize>);
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.0.update(|n| *n = n.saturating_sub(1));
     }
 }
 
+/// Calls a Tauri command and awaits its typed result, without any reactive state.
+///
+/// Useful from event handlers, actions or plain `async fn`s.
+/// 
+/// ```rust,ignore
+/// let on_save = move |_| {
+///     spawn_local(async move {
+///         match invoke_typed::<DemoModelWrapper, (), Demo>("create_demo", demo, ()).await {
+///             Ok(demo) => ...,
+///             Err(err) => ...,
+///         }
+///     });
+/// };
+/// ```
+pub async fn invoke_typed<Args, Opts, T>(
+    cmd: &'static str,
+    args: Args,
+    options: Opts,
+) -> Result<T, UseTauriError>
+where 
+    Args: serde::Serialize,
+    Opts: serde::Serialize,
+    T: serde::de::DeserializeOwned,
+{
+    let data = invoke_value(cmd, args, options).await?;
+    serde_wasm_bindgen::from_value::<T>(data)
+        .map_err(|err| UseTauriError::Deserialize(err.to_string()))
+}
+
+/// `invoke_typed` giving up with [`UseTauriError::Timeout`] if the command didn't resolve `after`,
+/// a late response is ignored.
+pub async fn invoke_with_timeout<Args, Opts, T>(
+    cmd: &'static str,
+    args: Args,
+    options: Opts,
+    after: Duration,
+) -> Result<T, UseTauriError>
+where 
+    Args: serde::Serialize,
+    Opts: serde::Serialize,
+    T: serde::de::DeserializeOwned,
+{
+    utils::timeout(after, invoke_typed(cmd, args, options))
+        .await
+        .unwrap_or(Err(UseTauriError::Timeout { cmd, after }))
+}
+
+/// Serializes the arguments and calls `invoke`, leaving the raw result to the caller
+pub(crate) async fn invoke_value<Args, Opts>(
+    cmd: &'static str,
+    args: Args,
+    options: Opts,
+) -> Result<JsValue, UseTauriError>
+where 
+    Args: serde::Serialize,
+    Opts: serde::Serialize,
+{
+    // maps must be plain objects, both for command args and the headers record
+    let serializer = serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true);
+
+    let args = args.serialize(&serializer)
+        .map_err(|err| UseTauriError::Serialize(err.to_string()))?;
+    let options = options.serialize(&serializer)
+        .map_err(|err| UseTauriError::Serialize(err.to_string()))?;
+
+    invoke(cmd, args, options)
+        .await
+        .map_err(|err| UseTauriError::from_rejection(cmd, err))
+}
+
+/// Future returned by the `execute` callable of the invoke hooks
+pub type InvokeFuture<T> = Pin<Box<dyn Future<Output = Result<T, UseTauriError>>>>;
+
 pub struct UseTauriReturn<Args, Opts, T>
 where 
-    Args: serde::Serialize + Clone + 'static,
-    Opts: serde::Serialize + Clone + 'static,
+    Args: serde::Serialize + Clone + Send + Sync + 'static,
+    Opts: serde::Serialize + Clone + Send + Sync + 'static,
     T: serde::de::DeserializeOwned + Clone + Send + Sync + 'static,
 {
     pub data: Signal<Option<T>>,
     pub error: Signal<Option<UseTauriError>, LocalStorage>,
+    /// `true` while at least one call of the command is in flight
+    pub pending: Signal<bool>,
+    /// Timestamp (ms since epoch) of the most recent call start
+    pub started_at: Signal<Option<f64>>,
+    /// Timestamp (ms since epoch) of the most recent call completion
+    pub finished_at: Signal<Option<f64>>,
+    /// Attempt number (starting at 1) of the most recent call, see [`RetryPolicy`]
+    pub attempt: Signal<u32>,
+    /// The value `data` held before the latest successful call, see [`UseInvokeConfig::history`]
+    pub previous: Signal<Option<T>>,
+    /// The latest successful results with their args and options, oldest first,
+    /// see [`UseInvokeConfig::history`]
+    pub history: Signal<VecDeque<InvokeRecord<(Args, Opts), T>>>,
     pub trigger: WriteSignal<Option<(Args, Opts)>>,
+    /// Calls the command right away and resolves with its result, also updating `data` and `error`.
+    /// 
+    /// Unlike `trigger` it bypasses the rate limit and the queue of [`InvokePolicy::Queue`].
+    /// It still resolves once the hook's owner is cleaned up, leaving the state untouched.
+    pub execute: Arc<dyn Fn(Args, Opts) -> InvokeFuture<T> + Send + Sync>,
+    /// `execute` giving up on every attempt not resolved within the given duration, 
+    /// instead of the timeout of [`UseInvokeConfig::timeout`]
+    pub execute_with_timeout: Arc<dyn Fn(Args, Opts, Duration) -> InvokeFuture<T> + Send + Sync>,
+    /// Clears every state signal, drops the trigger held back by the rate limit
+    /// and discards the results of calls still in flight
+    pub reset: Arc<dyn Fn() + Send + Sync>,
+    pub clear_error: Arc<dyn Fn() + Send + Sync>,
+    pub callbacks: InvokeCallbacks<T>,
+}
+
+impl<Args, Opts, T> UseTauriReturn<Args, Opts, T>
+where 
+    Args: serde::Serialize + Clone + Send + Sync + 'static,
+    Opts: serde::Serialize + Clone + Send + Sync + 'static,
+    T: serde::de::DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    /// Keeps the state signals while swapping the trigger, used by the alias functions.
+    /// `map` turns the new trigger's value into the args and options of the command,
+    /// `unmap` turns them back for the history.
+    pub(crate) fn with_trigger<O>(
+        self, 
+        trigger: WriteSignal<Option<O>>,
+        map: impl Fn(O) -> (Args, Opts) + Send + Sync + 'static,
+        unmap: impl Fn(&(Args, Opts)) -> O + Send + Sync + 'static,
+    ) -> UseTauriWithReturn<O, T>
+    where 
+        O: serde::Serialize + Clone + Send + Sync + 'static,
+    {
+        let execute = self.execute;
+        let execute_with_timeout = self.execute_with_timeout;
+        let history = self.history;
+        let map = Arc::new(map);
+
+        UseTauriWithReturn {
+            data: self.data,
+            error: self.error,
+            pending: self.pending,
+            started_at: self.started_at,
+            finished_at: self.finished_at,
+            attempt: self.attempt,
+            previous: self.previous,
+            history: Signal::derive(move || {
+                history.get().into_iter().map(|record| record.map_args(&unmap)).collect()
+            }),
+            trigger,
+            execute: {
+                let map = Arc::clone(&map);
+                Arc::new(move |value| {
+                    let (args, opts) = map(value);
+                    execute(args, opts)
+                })
+            },
+            execute_with_timeout: Arc::new(move |value, after| {
+                let (args, opts) = map(value);
+                execute_with_timeout(args, opts, after)
+            }),
+            reset: self.reset,
+            clear_error: self.clear_error,
+            callbacks: self.callbacks,
+        }
+    }
+}
+
+/// A successful call kept in the history of [`use_invoke_with_config`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvokeRecord<A, T> {
+    /// What the call was triggered with, the args and options for `use_invoke`
+    pub args: A,
+    pub data: T,
+    /// Timestamp (ms since epoch) of the completion
+    pub at: f64,
+}
+
+impl<A, T> InvokeRecord<A, T> {
+    fn map_args<A2>(self, map: impl Fn(&A) -> A2) -> InvokeRecord<A2, T> {
+        InvokeRecord {
+            args: map(&self.args),
+            data: self.data,
+            at: self.at,
+        }
+    }
 }
 
 pub struct UseTauriWithReturn<O, T>
 where 
-    O: serde::Serialize + Clone + 'static,
+    O: serde::Serialize + Clone + Send + Sync + 'static,
     T: serde::de::DeserializeOwned + Clone + Send + Sync + 'static,
 {
     pub data: Signal<Option<T>>,
     pub error: Signal<Option<UseTauriError>, LocalStorage>,
+    /// `true` while at least one call of the command is in flight
+    pub pending: Signal<bool>,
+    /// Timestamp (ms since epoch) of the most recent call start
+    pub started_at: Signal<Option<f64>>,
+    /// Timestamp (ms since epoch) of the most recent call completion
+    pub finished_at: Signal<Option<f64>>,
+    /// Attempt number (starting at 1) of the most recent call, see [`RetryPolicy`]
+    pub attempt: Signal<u32>,
+    /// The value `data` held before the latest successful call, see [`UseInvokeConfig::history`]
+    pub previous: Signal<Option<T>>,
+    /// The latest successful results with the trigger's value, oldest first,
+    /// see [`UseInvokeConfig::history`]
+    pub history: Signal<VecDeque<InvokeRecord<O, T>>>,
     pub trigger: WriteSignal<Option<O>>,
+    /// Calls the command right away and resolves with its result, also updating `data` and `error`.
+    /// It still resolves once the hook's owner is cleaned up, leaving the state untouched.
+    /// 
+    /// ```rust,ignore
+    /// let on_save = move |_| {
+    ///     let save = save.clone();
+    ///     spawn_local(async move {
+    ///         if save(demo()).await.is_ok() {
+    ///             reload(()).await;
+    ///             navigate("/demos", Default::default());
+    ///         }
+    ///     });
+    /// };
+    /// ```
+    pub execute: Arc<dyn Fn(O) -> InvokeFuture<T> + Send + Sync>,
+    /// `execute` giving up on every attempt not resolved within the given duration, 
+    /// instead of the timeout of [`UseInvokeConfig::timeout`]
+    /// 
+    /// ```rust,ignore
+    /// let export = execute_with_timeout(ExportArgs::all(), Duration::from_secs(120));
+    /// ```
+    pub execute_with_timeout: Arc<dyn Fn(O, Duration) -> InvokeFuture<T> + Send + Sync>,
+    /// Clears every state signal, drops the trigger held back by the rate limit
+    /// and discards the results of calls still in flight
+    pub reset: Arc<dyn Fn() + Send + Sync>,
+    pub clear_error: Arc<dyn Fn() + Send + Sync>,
+    pub callbacks: InvokeCallbacks<T>,
+}
+
+impl<O, T> UseTauriWithReturn<O, T>
+where 
+    O: serde::Serialize + Clone + Send + Sync + 'static,
+    T: serde::de::DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    /// Keeps the state signals while swapping the trigger, used by wrapping hooks.
+    /// `map` turns the new trigger's value into the one of the wrapped hook,
+    /// `unmap` turns it back for the history.
+    pub(crate) fn with_trigger<O2>(
+        self, 
+        trigger: WriteSignal<Option<O2>>,
+        map: impl Fn(O2) -> O + Send + Sync + 'static,
+        unmap: impl Fn(&O) -> O2 + Send + Sync + 'static,
+    ) -> UseTauriWithReturn<O2, T>
+    where 
+        O2: serde::Serialize + Clone + Send + Sync + 'static,
+    {
+        let execute = self.execute;
+        let execute_with_timeout = self.execute_with_timeout;
+        let history = self.history;
+        let map = Arc::new(map);
+
+        UseTauriWithReturn {
+            data: self.data,
+            error: self.error,
+            pending: self.pending,
+            started_at: self.started_at,
+            finished_at: self.finished_at,
+            attempt: self.attempt,
+            previous: self.previous,
+            history: Signal::derive(move || {
+                history.get().into_iter().map(|record| record.map_args(&unmap)).collect()
+            }),
+            trigger,
+            execute: {
+                let map = Arc::clone(&map);
+                Arc::new(move |value| execute(map(value)))
+            },
+            execute_with_timeout: Arc::new(move |value, after| execute_with_timeout(map(value), after)),
+            reset: self.reset,
+            clear_error: self.clear_error,
+            callbacks: self.callbacks,
+        }
+    }
+}
+
+/// Callbacks run once for every call whose result is applied to `data` or `error`,
+/// i.e. not for results discarded by the [`InvokePolicy`] or a reset
+/// 
+/// ```rust,ignore
+/// let UseTauriWithReturn { callbacks, .. } = use_invoke_with_args::<DemoModelWrapper, Demo>("create_demo");
+/// callbacks.on_success(|demo| toast(format!("{} created", demo.name)));
+/// callbacks.on_error(|err| toast(err.to_string()));
+/// ```
+pub struct InvokeCallbacks<T>
+where 
+    T: 'static,
+{
+    on_success: StoredValue<Vec<SuccessCallback<T>>>,
+    on_error: StoredValue<Vec<ErrorCallback>>,
+    on_settled: StoredValue<Vec<SettledCallback<T>>>,
+}
+
+type SuccessCallback<T> = Arc<dyn Fn(&T) + Send + Sync>;
+type ErrorCallback = Arc<dyn Fn(&UseTauriError) + Send + Sync>;
+type SettledCallback<T> = Arc<dyn Fn(&Result<T, UseTauriError>) + Send + Sync>;
+
+impl<T> InvokeCallbacks<T>
+where 
+    T: 'static,
+{
+    fn new(
+        on_success: Vec<SuccessCallback<T>>, 
+        on_error: Vec<ErrorCallback>,
+        on_settled: Vec<SettledCallback<T>>,
+    ) -> Self {
+        Self {
+            on_success: StoredValue::new(on_success),
+            on_error: StoredValue::new(on_error),
+            on_settled: StoredValue::new(on_settled),
+        }
+    }
+
+    pub fn on_success(&self, callback: impl Fn(&T) + Send + Sync + 'static) {
+        self.on_success.update_value(|callbacks| callbacks.push(Arc::new(callback)));
+    }
+
+    pub fn on_error(&self, callback: impl Fn(&UseTauriError) + Send + Sync + 'static) {
+        self.on_error.update_value(|callbacks| callbacks.push(Arc::new(callback)));
+    }
+
+    /// Runs after the success or error callbacks, whatever the outcome
+    pub fn on_settled(&self, callback: impl Fn(&Result<T, UseTauriError>) + Send + Sync + 'static) {
+        self.on_settled.update_value(|callbacks| callbacks.push(Arc::new(callback)));
+    }
+
+    fn run(&self, result: &Result<T, UseTauriError>) {
+        // cloned out so a callback can register further callbacks,
+        // none are left once the hook's owner was cleaned up
+        match result {
+            Ok(data) => {
+                for callback in self.on_success.try_get_value().unwrap_or_default() {
+                    callback(data);
+                }
+            }
+            Err(err) => {
+                for callback in self.on_error.try_get_value().unwrap_or_default() {
+                    callback(err);
+                }
+            }
+        }
+        for callback in self.on_settled.try_get_value().unwrap_or_default() {
+            callback(result);
+        }
+    }
+}
+
+impl<T> Clone for InvokeCallbacks<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for InvokeCallbacks<T> {}
+
+/// Options passed as the third argument of Tauri's `invoke`
+/// 
+/// ```rust,ignore
+/// let UseTauriReturn { trigger, .. } = use_invoke::<IdWrapper, InvokeOptions, Demo>("get_data");
+/// trigger.set(Some((
+///     IdWrapper::new(id),
+///     InvokeOptions::new().header("Authorization", format!("Bearer {token}")),
+/// )));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct InvokeOptions {
+    /// Request headers, readable on the Rust side through `tauri::ipc::Request::headers`
+    pub headers: BTreeMap<String, String>,
+}
+
+impl InvokeOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.insert(name.into(), value.into());
+        self
+    }
+}
+
+/// Options for [`use_invoke_with_config`]
+pub struct UseInvokeConfig<T> {
+    /// How a trigger is handled while previous calls are still in flight
+    pub policy: InvokePolicy,
+    /// Retries failed calls, disabled by default
+    pub retry: Option<RetryPolicy>,
+    /// Coalesces bursts of triggers before any call is made, disabled by default
+    pub rate_limit: Option<RateLimit>,
+    /// Gives up on every attempt not resolved within this duration, disabled by default
+    pub timeout: Option<Duration>,
+    /// Number of successful calls kept in `history`, disabled (zero) by default.
+    /// `previous` is only tracked when enabled.
+    pub history: usize,
+    on_success: Vec<SuccessCallback<T>>,
+    on_error: Vec<ErrorCallback>,
+    on_settled: Vec<SettledCallback<T>>,
+}
+
+impl<T> Default for UseInvokeConfig<T> {
+    fn default() -> Self {
+        Self {
+            policy: InvokePolicy::default(),
+            retry: None,
+            rate_limit: None,
+            timeout: None,
+            history: 0,
+            on_success: Vec::new(),
+            on_error: Vec::new(),
+            on_settled: Vec::new(),
+        }
+    }
+}
+
+impl<T> Clone for UseInvokeConfig<T> {
+    fn clone(&self) -> Self {
+        Self {
+            policy: self.policy,
+            retry: self.retry.clone(),
+            rate_limit: self.rate_limit,
+            timeout: self.timeout,
+            history: self.history,
+            on_success: self.on_success.clone(),
+            on_error: self.on_error.clone(),
+            on_settled: self.on_settled.clone(),
+        }
+    }
+}
+
+impl<T> UseInvokeConfig<T> {
+    pub fn policy(mut self, policy: InvokePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = Some(retry);
+        self
+    }
+
+    pub fn rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.rate_limit = Some(rate_limit);
+        self
+    }
+
+    /// Shorthand for [`RateLimit::debounce`]
+    pub fn debounce(self, wait: Duration) -> Self {
+        self.rate_limit(RateLimit::debounce(wait))
+    }
+
+    /// Shorthand for [`RateLimit::throttle`]
+    pub fn throttle(self, wait: Duration) -> Self {
+        self.rate_limit(RateLimit::throttle(wait))
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn history(mut self, history: usize) -> Self {
+        self.history = history;
+        self
+    }
+
+    /// See [`InvokeCallbacks::on_success`]
+    pub fn on_success(mut self, callback: impl Fn(&T) + Send + Sync + 'static) -> Self {
+        self.on_success.push(Arc::new(callback));
+        self
+    }
+
+    /// See [`InvokeCallbacks::on_error`]
+    pub fn on_error(mut self, callback: impl Fn(&UseTauriError) + Send + Sync + 'static) -> Self {
+        self.on_error.push(Arc::new(callback));
+        self
+    }
+
+    /// See [`InvokeCallbacks::on_settled`]
+    pub fn on_settled(mut self, callback: impl Fn(&Result<T, UseTauriError>) + Send + Sync + 'static) -> Self {
+        self.on_settled.push(Arc::new(callback));
+        self
+    }
+}
+
+/// Debounce or throttle configuration for triggers, with the same semantics as lodash's `debounce`
+/// 
+/// ```rust,ignore
+/// // search as you type, but at least once a second while typing
+/// let config = UseInvokeConfig::default().rate_limit(
+///     RateLimit::debounce(Duration::from_millis(300)).max_wait(Duration::from_secs(1)),
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    /// A burst of triggers ends once there was none for this long
+    pub wait: Duration,
+    /// Call with the first trigger of a burst
+    pub leading: bool,
+    /// Call with the latest trigger once the burst ended
+    pub trailing: bool,
+    /// Longest time a trigger can be delayed during a burst, never shorter than `wait`
+    pub max_wait: Option<Duration>,
+}
+
+impl RateLimit {
+    /// Calls once triggers stopped for `wait`, with the latest trigger
+    pub fn debounce(wait: Duration) -> Self {
+        Self {
+            wait,
+            leading: false,
+            trailing: true,
+            max_wait: None,
+        }
+    }
+
+    /// Calls at most once per `wait`, on the leading and trailing edge
+    pub fn throttle(wait: Duration) -> Self {
+        Self {
+            wait,
+            leading: true,
+            trailing: true,
+            max_wait: Some(wait),
+        }
+    }
+
+    pub fn leading(mut self, leading: bool) -> Self {
+        self.leading = leading;
+        self
+    }
+
+    pub fn trailing(mut self, trailing: bool) -> Self {
+        self.trailing = trailing;
+        self
+    }
+
+    pub fn max_wait(mut self, max_wait: Duration) -> Self {
+        self.max_wait = Some(max_wait);
+        self
+    }
+}
+
+/// Concurrency policy for overlapping calls of the same command
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InvokePolicy {
+    /// Every trigger starts a call, only the result of the most recently started one is kept
+    #[default]
+    LatestWins,
+    /// Triggers are queued and called one after another
+    Queue,
+    /// Triggers are ignored while a call is in flight
+    DropWhilePending,
+    /// Every trigger starts a call, results are kept unless a newer call already resolved
+    Parallel,
+}
+
+/// How often and when a failed call is retried
+/// 
+/// ```rust,ignore
+/// let config = UseInvokeConfig::default().retry(
+///     RetryPolicy::new(5)
+///         .backoff(Backoff::Exponential {
+///             initial: Duration::from_millis(200),
+///             max: Duration::from_secs(5),
+///             jitter: true,
+///         })
+///         .retry_if(|err| matches!(err, UseTauriError::Command(..))),
+/// );
+/// ```
+#[derive(Clone)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first call
+    pub max_attempts: u32,
+    pub backoff: Backoff,
+    /// Decides whether an error is worth retrying, defaults to command errors only
+    pub retry_if: Arc<dyn Fn(&UseTauriError) -> bool + Send + Sync>,
+}
+
+impl RetryPolicy {
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            ..Default::default()
+        }
+    }
+
+    pub fn backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    pub fn retry_if(mut self, retry_if: impl Fn(&UseTauriError) -> bool + Send + Sync + 'static) -> Self {
+        self.retry_if = Arc::new(retry_if);
+        self
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            backoff: Backoff::default(),
+            retry_if: Arc::new(|err| matches!(err, UseTauriError::Command(..) | UseTauriError::CommandValue(..))),
+        }
+    }
+}
+
+impl fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("backoff", &self.backoff)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Delay between two attempts of a [`RetryPolicy`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Backoff {
+    Fixed(Duration),
+    /// Doubles the delay after every attempt, up to `max`. 
+    /// With `jitter` the delay is randomized between half and the full value.
+    Exponential {
+        initial: Duration,
+        max: Duration,
+        jitter: bool,
+    },
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Backoff::Fixed(Duration::from_millis(500))
+    }
+}
+
+impl Backoff {
+    /// Delay before the attempt following `attempt`
+    fn delay(&self, attempt: u32) -> Duration {
+        match *self {
+            Backoff::Fixed(delay) => delay,
+            Backoff::Exponential { initial, max, jitter } => {
+                let delay = initial
+                    .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+                    .min(max);
+                if jitter {
+                    delay.mul_f64(0.5 + js_sys::Math::random() / 2.0)
+                } else {
+                    delay
+                }
+            }
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
 pub enum UseTauriError {
+    /// The command rejected with a string
     Command(&'static str, String),
+    /// The command rejected with a structured value, kept as raw JSON
+    CommandValue(&'static str, String),
     Serialize(String),
     Deserialize(String),
+    /// The command didn't resolve in time, see [`UseInvokeConfig::timeout`]
+    Timeout {
+        cmd: &'static str,
+        after: Duration,
+    },
+}
+
+impl UseTauriError {
+    fn from_rejection(cmd: &'static str, err: JsValue) -> Self {
+        // anything but a message is kept as JSON, `undefined` has none
+        let is_value = !err.is_string() && !err.is_instance_of::<js_sys::Error>() && !err.is_undefined();
+        let message = utils::rejection_message(err);
+
+        if is_value {
+            UseTauriError::CommandValue(cmd, message)
+        } else {
+            UseTauriError::Command(cmd, message)
+        }
+    }
+
+    /// Deserializes the value a command rejected with into the command's own error type,
+    /// e.g. a `#[derive(Serialize)]` error enum on the Rust side.
+    /// 
+    /// Returns `None` for non-command errors or when the value doesn't match `E`.
+    /// 
+    /// ```rust,ignore
+    /// if let Some(err) = error.get() {
+    ///     match err.command_value::<DemoError>() {
+    ///         Some(DemoError::NotFound(id)) => ...,
+    ///         _ => ...,
+    ///     }
+    /// }
+    /// ```
+    pub fn command_value<E>(&self) -> Option<E>
+    where 
+        E: serde::de::DeserializeOwned,
+    {
+        let value = match self {
+            UseTauriError::Command(_, err) => JsValue::from_str(err),
+            UseTauriError::CommandValue(_, json) => js_sys::JSON::parse(json).ok()?,
+            _ => return None,
+        };
+        serde_wasm_bindgen::from_value(value).ok()
+    }
 }
 
 impl fmt::Display for UseTauriError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
          match self {
             UseTauriError::Command(place, err) => write!(f, "Command error in {}: {}", place, err),
+            UseTauriError::CommandValue(place, json) => write!(f, "Command error in {}: {}", place, json),
             UseTauriError::Serialize(err) => write!(f, "Error serializing value: {}", err),
             UseTauriError::Deserialize(err) => write!(f, "Error deserializing value: {}", err),
+            UseTauriError::Timeout { cmd, after } => write!(f, "Command {} timed out after {:?}", cmd, after),
         }
     }
 }
@@ -0,0 +1,100 @@
+use js_sys::Uint8Array;
+use serde::{Serialize, Serializer};
+use wasm_bindgen::JsCast as _;
+
+use crate::{invoke_value, use_invoke_inner, UseInvokeConfig, UseTauriError, UseTauriReturn};
+
+/// Raw bytes sent as the whole request body instead of JSON arguments,
+/// received on the Rust side through `tauri::ipc::Request::body`.
+/// 
+/// ```rust,ignore
+/// let UseTauriReturn { trigger: upload, .. } = use_invoke::<RawBody, (), ()>("upload");
+/// upload.set(Some((RawBody::from(bytes), ())));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RawBody(pub Vec<u8>);
+
+impl From<Vec<u8>> for RawBody {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+impl Serialize for RawBody {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        // `serde_wasm_bindgen` turns bytes into a `Uint8Array`, which `invoke` sends as a raw body
+        serializer.serialize_bytes(&self.0)
+    }
+}
+
+/// Calls a command returning binary data, e.g. a `tauri::ipc::Response`, 
+/// and copies the body into a `Vec<u8>` without going through serde.
+pub async fn invoke_bytes<Args, Opts>(
+    cmd: &'static str,
+    args: Args,
+    options: Opts,
+) -> Result<Vec<u8>, UseTauriError>
+where
+    Args: serde::Serialize,
+    Opts: serde::Serialize,
+{
+    let data = invoke_value(cmd, args, options).await?;
+
+    if data.is_instance_of::<js_sys::ArrayBuffer>() || data.is_instance_of::<Uint8Array>() {
+        Ok(Uint8Array::new(&data).to_vec())
+    } else {
+        // commands returning a plain `Vec<u8>` are serialized as a JSON array
+        serde_wasm_bindgen::from_value::<Vec<u8>>(data)
+            .map_err(|err| UseTauriError::Deserialize(err.to_string()))
+    }
+}
+
+/// `use_invoke` for commands returning binary data, see [`invoke_bytes`]
+/// 
+/// ```rust,ignore
+/// #[component]
+/// pub fn Demo() -> impl IntoView {
+///     let UseTauriReturn {
+///         data: image,
+///         trigger: load,
+///         ..
+///     } = use_invoke_bytes::<PathWrapper, ()>("read_image");
+///
+///     view!{
+///         ...
+///         <button on:click=move |_| load.set(Some((PathWrapper::new(path), ())))>load</button>
+///         ...
+///     }
+/// }
+/// ```
+pub fn use_invoke_bytes<Args, Opts>(
+    cmd: &'static str,
+) -> UseTauriReturn<Args, Opts, Vec<u8>>
+where
+    Args: serde::Serialize + Clone + Send + Sync + 'static,
+    Opts: serde::Serialize + Clone + Send + Sync + 'static,
+{
+    use_invoke_bytes_with_config(cmd, UseInvokeConfig::default())
+}
+
+/// `use_invoke_bytes` with the configuration of [`use_invoke_with_config`](crate::use_invoke_with_config)
+/// 
+/// ```rust,ignore
+/// let UseTauriReturn { data: image, trigger: load, .. } = use_invoke_bytes_with_config::<PathWrapper, ()>(
+///     "read_image",
+///     UseInvokeConfig::default().timeout(Duration::from_secs(10)),
+/// );
+/// ```
+pub fn use_invoke_bytes_with_config<Args, Opts>(
+    cmd: &'static str,
+    config: UseInvokeConfig<Vec<u8>>,
+) -> UseTauriReturn<Args, Opts, Vec<u8>>
+where
+    Args: serde::Serialize + Clone + Send + Sync + 'static,
+    Opts: serde::Serialize + Clone + Send + Sync + 'static,
+{
+    use_invoke_inner(cmd, config, invoke_bytes::<Args, Opts>)
+}
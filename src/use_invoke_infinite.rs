@@ -0,0 +1,159 @@
+use std::sync::Arc;
+use reactive_graph::{
+    effect::Effect,
+    owner::{LocalStorage, StoredValue},
+    signal::{signal, signal_local},
+    spawn_local_scoped,
+    traits::{GetUntracked as _, GetValue as _, Set as _, SetValue as _, Track as _, Update as _, UpdateValue as _},
+    wrappers::read::Signal,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{invoke_typed, UseTauriError};
+
+/// Calls a paginated command page by page, accumulating the items of every page.
+///
+/// The command receives the fields of `args` along with `cursor` and `limit`, and returns
+/// `{ items, next_cursor }`, see [`PageRequest`] and [`Page`]. The first page is fetched right
+/// away and again whenever `args` changes.
+///
+/// ```rust,ignore
+/// #[component]
+/// pub fn Demo() -> impl IntoView {
+///     let (filter, set_filter) = signal(DemoFilter::default());
+///
+///     let UseInvokeInfiniteReturn {
+///         items: demos,
+///         has_more,
+///         fetch_next,
+///         ..
+///     } = use_invoke_infinite::<DemoFilter, Demo, u32>("list_data", filter, 50);
+///
+///     view!{
+///         ...
+///         <Show when=move || has_more.get()>
+///             <button on:click=move |_| fetch_next()>load more</button>
+///         </Show>
+///         ...
+///     }
+/// }
+/// ```
+pub fn use_invoke_infinite<Args, I, C>(
+    cmd: &'static str,
+    args: impl Into<Signal<Args>>,
+    limit: u32,
+) -> UseInvokeInfiniteReturn<I>
+where
+    Args: Serialize + Clone + Send + Sync + 'static,
+    I: serde::de::DeserializeOwned + Clone + Send + Sync + 'static,
+    C: Serialize + serde::de::DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    let args = args.into();
+
+    let (items, set_items) = signal(Vec::<I>::new());
+    let (error, set_error) = signal_local(None::<UseTauriError>);
+    let (pending, set_pending) = signal(false);
+    let (has_more, set_has_more) = signal(true);
+
+    let cursor = StoredValue::new(None::<C>);
+    // bumped when the pages start over, so pages of the previous run are dropped
+    let generation = StoredValue::new(0u64);
+
+    let load = move |first: bool| {
+        if first {
+            generation.update_value(|n| *n += 1);
+            cursor.set_value(None);
+            set_has_more.set(true);
+        } else if pending.get_untracked() || !has_more.get_untracked() {
+            return;
+        }
+
+        let id = generation.get_value();
+        let request = PageRequest {
+            args: args.get_untracked(),
+            cursor: cursor.get_value(),
+            limit,
+        };
+        set_pending.set(true);
+        set_error.set(None);
+
+        spawn_local_scoped(async move {
+            let result = invoke_typed::<PageRequest<Args, C>, (), Page<I, C>>(cmd, request, ()).await;
+            // also dropped once the owner is cleaned up
+            if generation.try_get_value() != Some(id) {
+                return;
+            }
+
+            match result {
+                Ok(page) => {
+                    if first {
+                        set_items.set(page.items);
+                    } else {
+                        set_items.update(|items| items.extend(page.items));
+                    }
+                    set_has_more.set(page.next_cursor.is_some());
+                    cursor.set_value(page.next_cursor);
+                    set_error.set(None);
+                }
+                Err(err) => {
+                    // the items of the previous args must not be extended with the new ones
+                    if first {
+                        set_items.set(Vec::new());
+                    }
+                    set_error.set(Some(err));
+                }
+            }
+            set_pending.set(false);
+        });
+    };
+
+    Effect::new(move || {
+        args.track();
+        load(true);
+    });
+
+    UseInvokeInfiniteReturn {
+        items: items.into(),
+        error: error.into(),
+        pending: pending.into(),
+        has_more: has_more.into(),
+        fetch_next: Arc::new(move || load(false)),
+        refetch: Arc::new(move || load(true)),
+    }
+}
+
+/// Args sent by [`use_invoke_infinite`] for every page
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PageRequest<Args, C> {
+    #[serde(flatten)]
+    pub args: Args,
+    /// `None` for the first page
+    pub cursor: Option<C>,
+    pub limit: u32,
+}
+
+/// Page returned by a command used with [`use_invoke_infinite`]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Page<I, C> {
+    pub items: Vec<I>,
+    /// `None` once the last page is reached
+    #[serde(alias = "nextCursor")]
+    pub next_cursor: Option<C>,
+}
+
+pub struct UseInvokeInfiniteReturn<I>
+where
+    I: serde::de::DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    /// Items of every page fetched so far, in order
+    pub items: Signal<Vec<I>>,
+    pub error: Signal<Option<UseTauriError>, LocalStorage>,
+    /// `true` while a page is being fetched
+    pub pending: Signal<bool>,
+    /// `false` once the last page is fetched
+    pub has_more: Signal<bool>,
+    /// Fetches the next page, does nothing while a page is pending or when there is none left
+    pub fetch_next: Arc<dyn Fn() + Send + Sync>,
+    /// Fetches the first page again, replacing the items once it arrives
+    pub refetch: Arc<dyn Fn() + Send + Sync>,
+}
@@ -0,0 +1,44 @@
+use reactive_graph::{
+    computed::AsyncDerived,
+    traits::Get as _,
+    wrappers::read::Signal,
+};
+
+use crate::{invoke_typed, UseTauriError};
+
+/// Invokes a command whenever `args` changes and exposes the result as an async derived value,
+/// which can be awaited inside `<Suspense>` or `<Transition>`.
+///
+/// ```rust,ignore
+/// #[component]
+/// pub fn Demo() -> impl IntoView {
+///     let (id, set_id) = signal(IdWrapper::new(1));
+///     let demo = use_invoke_resource::<IdWrapper, Demo>("get_data", id);
+///
+///     view!{
+///         <Suspense fallback=|| "loading...">
+///             {move || Suspend::new(async move {
+///                 match demo.await {
+///                     Ok(demo) => ...,
+///                     Err(err) => ...,
+///                 }
+///             })}
+///         </Suspense>
+///     }
+/// }
+/// ```
+pub fn use_invoke_resource<Args, T>(
+    cmd: &'static str,
+    args: impl Into<Signal<Args>>,
+) -> AsyncDerived<Result<T, UseTauriError>>
+where
+    Args: serde::Serialize + Clone + Send + Sync + 'static,
+    T: serde::de::DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    let args = args.into();
+
+    AsyncDerived::new_unsync_threadsafe_storage(move || {
+        let args = args.get();
+        async move { invoke_typed::<Args, (), T>(cmd, args, ()).await }
+    })
+}
@@ -0,0 +1,137 @@
+use std::{sync::Arc, time::Duration};
+use reactive_graph::{
+    effect::Effect,
+    signal::signal,
+    traits::{Get as _, Set as _, UpdateUntracked as _},
+    wrappers::read::Signal,
+};
+
+use crate::{
+    utils::rate_limited, use_invoke_with_config, InvokePolicy, RateLimit, UseInvokeConfig, UseTauriWithReturn,
+};
+
+/// Invokes a command whenever `source` changes, dropping the results of superseded calls.
+///
+/// The returned trigger invokes the command right away, e.g. for a manual refresh.
+///
+/// ```rust,ignore
+/// #[component]
+/// pub fn Demo() -> impl IntoView {
+///     let (query, set_query) = signal(SearchQuery::default());
+///
+///     let UseTauriWithReturn {
+///         data: results,
+///         ..
+///     } = use_invoke_watch::<SearchQuery, Vec<Demo>>(
+///         "search",
+///         query,
+///         UseInvokeWatchConfig::default().debounce(Duration::from_millis(300)),
+///     );
+///
+///     view!{
+///         ...
+///         <input on:input=move |ev| set_query.set(SearchQuery::new(event_target_value(&ev))) />
+///         ...
+///     }
+/// }
+/// ```
+pub fn use_invoke_watch<Args, T>(
+    cmd: &'static str,
+    source: impl Into<Signal<Args>>,
+    config: UseInvokeWatchConfig,
+) -> UseTauriWithReturn<Args, T>
+where
+    Args: serde::Serialize + Clone + Send + Sync + 'static,
+    T: serde::de::DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    use_invoke_watch_with_config(cmd, source, config, UseInvokeConfig::default().policy(InvokePolicy::LatestWins))
+}
+
+/// `use_invoke_watch` with the configuration of [`use_invoke_with_config`], e.g. retries or a timeout.
+///
+/// Superseded results are only dropped with [`InvokePolicy::LatestWins`], the default policy.
+/// A rate limit of `invoke_config` applies to the returned trigger as well as to source changes,
+/// after the one of `config`.
+///
+/// ```rust,ignore
+/// let UseTauriWithReturn { data: results, .. } = use_invoke_watch_with_config::<SearchQuery, Vec<Demo>>(
+///     "search",
+///     query,
+///     UseInvokeWatchConfig::default().debounce(Duration::from_millis(300)),
+///     UseInvokeConfig::default().timeout(Duration::from_secs(5)),
+/// );
+/// ```
+pub fn use_invoke_watch_with_config<Args, T>(
+    cmd: &'static str,
+    source: impl Into<Signal<Args>>,
+    config: UseInvokeWatchConfig,
+    invoke_config: UseInvokeConfig<T>,
+) -> UseTauriWithReturn<Args, T>
+where
+    Args: serde::Serialize + Clone + Send + Sync + 'static,
+    T: serde::de::DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    let UseInvokeWatchConfig { immediate, rate_limit } = config;
+    let source = source.into();
+
+    let (args, set_args) = signal(None::<Args>);
+
+    let mut ret = use_invoke_with_config::<Args, (), T>(cmd, invoke_config);
+    let trigger = ret.trigger;
+
+    let (call, cancel) = rate_limited(rate_limit, move |args: Args| trigger.set(Some((args, ()))));
+
+    // a source change held back by the rate limit must not fill the state again
+    let reset = ret.reset;
+    ret.reset = Arc::new(move || {
+        cancel();
+        reset();
+    });
+
+    Effect::new(move |prev: Option<()>| {
+        let args = source.get();
+        if prev.is_some() || immediate {
+            call(args);
+        }
+    });
+
+    Effect::new(move || {
+        if let Some(args) = args.get() {
+            trigger.set(Some((args, ())));
+            set_args.update_untracked(|v| *v = None);
+        }
+    });
+
+    ret.with_trigger(set_args, |args| (args, ()), |(args, ())| args.clone())
+}
+
+/// Options for [`use_invoke_watch`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UseInvokeWatchConfig {
+    /// Also invoke with the initial value of the source, not only on changes
+    pub immediate: bool,
+    /// Coalesces bursts of source changes, disabled by default
+    pub rate_limit: Option<RateLimit>,
+}
+
+impl UseInvokeWatchConfig {
+    pub fn immediate(mut self, immediate: bool) -> Self {
+        self.immediate = immediate;
+        self
+    }
+
+    pub fn rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.rate_limit = Some(rate_limit);
+        self
+    }
+
+    /// Shorthand for [`RateLimit::debounce`]
+    pub fn debounce(self, wait: Duration) -> Self {
+        self.rate_limit(RateLimit::debounce(wait))
+    }
+
+    /// Shorthand for [`RateLimit::throttle`]
+    pub fn throttle(self, wait: Duration) -> Self {
+        self.rate_limit(RateLimit::throttle(wait))
+    }
+}
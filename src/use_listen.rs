@@ -1,18 +1,41 @@
 use core::fmt;
-use std::sync::{atomic::AtomicBool, Arc};
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    pin::Pin,
+    rc::Rc,
+    sync::{atomic::{AtomicBool, AtomicUsize}, Arc},
+    task::{Context, Poll},
+};
+use futures_channel::mpsc;
+use futures_core::Stream;
 use reactive_graph::{
+    effect::Effect,
     owner::{on_cleanup, LocalStorage, StoredValue}, 
     signal::{signal, signal_local}, 
     spawn_local_scoped, 
-    traits::{GetUntracked as _, GetValue as _, Set as _, SetValue as _}, 
+    traits::{GetUntracked as _, GetValue as _, Set as _, SetValue as _, Track as _}, 
     wrappers::read::Signal
 };
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use js_sys::Function;
 use wasm_bindgen::prelude::*;
 
+use crate::utils::rejection_message;
+
+/// Listens to `event` once `open` is called, keeping the latest payload in `data`.
+///
+/// `event` may be a signal, e.g. for per-entity events, changing it while open resubscribes to the
+/// new name.
+///
+/// ```rust,ignore
+/// let (job_id, set_job_id) = signal(1u32);
+/// let UseListenReturn { data: progress, open, .. } = use_listen::<f32>(Signal::derive(move || {
+///     EventType::custom(format!("job://{}/progress", job_id.get()))
+/// }));
+/// ```
 pub fn use_listen<T>(
-    event: EventType,
+    event: impl Into<Signal<EventType>>,
 ) -> UseListenReturn<
     T,
     impl Fn() + Clone + Send + Sync + 'static,
@@ -21,11 +44,11 @@ pub fn use_listen<T>(
 where 
     T: DeserializeOwned + Clone + PartialEq + Send + Sync + 'static,
 {
-    use_listen_inner::<T, _>(event.as_str(), listen, EventTarget::default())
+    use_listen_inner::<T, _, _>(event.into(), listen, EventTarget::default(), |_| {})
 }
 
 pub fn use_listen_with_target<T>(
-    event: EventType,
+    event: impl Into<Signal<EventType>>,
     target: EventTarget
 ) -> UseListenReturn<
     T,
@@ -35,11 +58,11 @@ pub fn use_listen_with_target<T>(
 where 
     T: DeserializeOwned + Clone + PartialEq + Send + Sync + 'static,
 {
-    use_listen_inner::<T, _>(event.as_str(), once, target)
+    use_listen_inner::<T, _, _>(event.into(), listen, target, |_| {})
 }
 
 pub fn use_once_listen<T>(
-    event: EventType,
+    event: impl Into<Signal<EventType>>,
 ) -> UseListenReturn<
     T,
     impl Fn() + Clone + Send + Sync + 'static,
@@ -48,11 +71,11 @@ pub fn use_once_listen<T>(
 where 
     T: DeserializeOwned + Clone + PartialEq + Send + Sync + 'static,
 {
-    use_listen_inner::<T, _>(event.as_str(), once, EventTarget::default())
+    use_listen_inner::<T, _, _>(event.into(), once, EventTarget::default(), |_| {})
 }
 
 pub fn use_once_listen_with_target<T>(
-    event: EventType,
+    event: impl Into<Signal<EventType>>,
     target: EventTarget
 ) -> UseListenReturn<
     T,
@@ -62,13 +85,14 @@ pub fn use_once_listen_with_target<T>(
 where 
     T: DeserializeOwned + Clone + PartialEq + Send + Sync + 'static,
 {
-    use_listen_inner::<T, _>(event.as_str(), once, target)
+    use_listen_inner::<T, _, _>(event.into(), once, target, |_| {})
 }
 
-fn use_listen_inner<T, F>(
-    event: &'static str,
+pub(crate) fn use_listen_inner<T, F, E>(
+    event: Signal<EventType>,
     callback: F,
     target: EventTarget,
+    on_event: E,
 ) -> UseListenReturn<
     T,
     impl Fn() + Clone + Send + Sync + 'static,
@@ -77,22 +101,30 @@ fn use_listen_inner<T, F>(
 where
     T: DeserializeOwned + Clone + PartialEq + Send + Sync + 'static,
     F: AsyncFn(&str, &Closure<dyn Fn(JsValue)>, JsValue) -> Result<JsValue, JsValue> + Send + Sync + 'static,
+    E: Fn(&T) + Send + Sync + 'static,
 {
     let (event_id, set_event_id) = signal(None::<u32>);
     let (data, set_data) = signal(None::<T>);
     let (error, set_error) = signal_local(None::<UseListenError>);
     let (unlisten, set_unlisten) = signal_local(None::<Arc<dyn Fn() + Send + Sync>>);
-    let explicitly_closed = Arc::new(AtomicBool::new(false));
+    // nothing is listened to until the first `open`
+    let explicitly_closed = Arc::new(AtomicBool::new(true));
+    // bumped by every close, so a subscription still pending at that point is dropped once it resolves
+    let generation = Arc::new(AtomicUsize::new(0));
     let callback = Arc::new(callback);
+    // sees every payload, unlike `data` which only keeps the latest one
+    let on_event = Arc::new(on_event);
 
     let close = {
         let explicitly_closed = Arc::clone(&explicitly_closed);
+        let generation = Arc::clone(&generation);
 
         let wrapped = send_wrapper::SendWrapper::new(move || {
+            generation.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            explicitly_closed.store(true, std::sync::atomic::Ordering::Relaxed);
             if let Some(unlisten) = unlisten.get_untracked() {
                 unlisten();
                 set_unlisten.set(None);
-                explicitly_closed.store(true, std::sync::atomic::Ordering::Relaxed);
             }
         });
 
@@ -106,6 +138,7 @@ where
 
     init.set_value(Some(Arc::new({
         let explicitly_closed = Arc::clone(&explicitly_closed);
+        let generation = Arc::clone(&generation);
 
         move || {
             use wasm_bindgen::prelude::*;
@@ -113,10 +146,20 @@ where
             if explicitly_closed.load(std::sync::atomic::Ordering::Relaxed) {
                 return;
             }
+            let id = generation.load(std::sync::atomic::Ordering::Relaxed);
+            let event = match event.get_untracked().name() {
+                Ok(event) => event,
+                Err(err) => {
+                    set_error.set(Some(err));
+                    return;
+                }
+            };
 
+            let on_event = Arc::clone(&on_event);
             let on_msg = Closure::wrap(Box::new(move |event: JsValue| {
                 match serde_wasm_bindgen::from_value::<Event<T>>(event) {
                     Ok(evt) => {
+                        on_event(&evt.payload);
                         set_data.set(Some(evt.payload));
                         set_event_id.set(Some(evt.id));
                     }
@@ -127,7 +170,8 @@ where
             }) as Box<dyn Fn(JsValue)>);
 
             let callback = callback.clone();
-            
+            let generation = Arc::clone(&generation);
+
             spawn_local_scoped(async move {
                 let options = match serde_wasm_bindgen::to_value(&target) {
                     Ok(opt) => opt,
@@ -137,22 +181,28 @@ where
                     }
                 };
 
-                match callback(event, &on_msg, options).await {
+                match callback(&event, &on_msg, options).await {
                     Ok(unlisten) => {
+                        // kept alive until unlistened
+                        let on_msg = RefCell::new(Some(on_msg));
                         let wrapped = send_wrapper::SendWrapper::new(move || {
                             let call: &Function = unlisten.unchecked_ref();
                             js_sys::Function::apply(call, &JsValue::NULL, &js_sys::Array::new()).unwrap();
+                            on_msg.borrow_mut().take();
                         });
+                        if generation.load(std::sync::atomic::Ordering::Relaxed) != id {
+                            wrapped();
+                            return;
+                        }
                         set_unlisten.set(Some(Arc::new(move || {
                             wrapped()
                         })));
                     }
                     Err(err) => {
-                        let err_str = err.as_string().unwrap_or_else(|| "Unknown error".to_string());
+                        let err_str = rejection_message(err);
                         set_error.set(Some(UseListenError::Event(event, err_str)))
                     }
                 }
-                on_msg.forget();
             });
         }
     })));
@@ -173,6 +223,19 @@ where
         }
     };
 
+    // resubscribes to the new name when the event changes while open
+    Effect::new({
+        let explicitly_closed = Arc::clone(&explicitly_closed);
+        let open = open.clone();
+
+        move |prev: Option<()>| {
+            event.track();
+            if prev.is_some() && !explicitly_closed.load(std::sync::atomic::Ordering::Relaxed) {
+                open();
+            }
+        }
+    });
+
     on_cleanup(close.clone());
 
     UseListenReturn { 
@@ -184,8 +247,112 @@ where
     }
 } 
 
+/// Listens to `event` outside of any reactive scope, yielding every event received.
+///
+/// Failing to listen or to deserialize a payload is yielded as an error, the stream ends after
+/// a failure to listen, e.g. for an invalid event name. Dropping the stream unlistens.
+///
+/// ```rust,ignore
+/// spawn_local(async move {
+///     let mut events = listen_stream::<SyncStatus>(EventType::custom("app://sync"), EventTarget::App);
+///     while let Some(evt) = events.next().await {
+///         match evt {
+///             Ok(evt) => ...,
+///             Err(err) => ...,
+///         }
+///     }
+/// });
+/// ```
+pub fn listen_stream<T>(event: EventType, target: EventTarget) -> EventStream<T>
+where
+    T: DeserializeOwned + 'static,
+{
+    let (sender, receiver) = mpsc::unbounded();
+    let state = Rc::new(RefCell::new(EventStreamState::default()));
+
+    let event = match event.name() {
+        Ok(event) => event,
+        Err(err) => {
+            let _ = sender.unbounded_send(Err(err));
+            return EventStream { receiver, state };
+        }
+    };
+
+    let on_msg = {
+        let sender = sender.clone();
+        Closure::wrap(Box::new(move |evt: JsValue| {
+            let evt = serde_wasm_bindgen::from_value::<Event<T>>(evt)
+                .map_err(|err| UseListenError::Deserialize(err.to_string()));
+            let _ = sender.unbounded_send(evt);
+        }) as Box<dyn Fn(JsValue)>)
+    };
+
+    let task_state = Rc::clone(&state);
+    wasm_bindgen_futures::spawn_local(async move {
+        let options = match serde_wasm_bindgen::to_value(&target) {
+            Ok(opt) => opt,
+            Err(err) => {
+                let _ = sender.unbounded_send(Err(UseListenError::Serialize(err.to_string())));
+                return;
+            }
+        };
+
+        match listen(&event, &on_msg, options).await {
+            Ok(unlisten) => {
+                let unlisten: Function = unlisten.unchecked_into();
+                let mut state = task_state.borrow_mut();
+                if state.dropped {
+                    let _ = unlisten.call0(&JsValue::NULL);
+                } else {
+                    state.unlisten = Some(unlisten);
+                    state.on_msg = Some(on_msg);
+                }
+            }
+            Err(err) => {
+                let err_str = rejection_message(err);
+                let _ = sender.unbounded_send(Err(UseListenError::Event(event, err_str)));
+            }
+        }
+    });
+
+    EventStream { receiver, state }
+}
+
+/// Stream returned by [`listen_stream`]
+pub struct EventStream<T> {
+    receiver: mpsc::UnboundedReceiver<Result<Event<T>, UseListenError>>,
+    state: Rc<RefCell<EventStreamState>>,
+}
+
+#[derive(Default)]
+struct EventStreamState {
+    dropped: bool,
+    unlisten: Option<Function>,
+    // kept alive until unlistened
+    on_msg: Option<Closure<dyn Fn(JsValue)>>,
+}
+
+impl<T> Stream for EventStream<T> {
+    type Item = Result<Event<T>, UseListenError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.get_mut().receiver).poll_next(cx)
+    }
+}
+
+impl<T> Drop for EventStream<T> {
+    fn drop(&mut self) {
+        let mut state = self.state.borrow_mut();
+        state.dropped = true;
+        if let Some(unlisten) = state.unlisten.take() {
+            let _ = unlisten.call0(&JsValue::NULL);
+        }
+        state.on_msg = None;
+    }
+}
+
 // reference: https://github.com/tauri-apps/tauri/blob/dev/packages/api/src/event.ts
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum EventType {
     WindowResized,
     WindowMoved,
@@ -201,34 +368,44 @@ pub enum EventType {
     DragOver,
     DragDrop,
     DragLeave,
-    Custom(&'static str)
+    Custom(Cow<'static, str>)
 }
 
 impl EventType {
-    fn as_str(&self) -> &'static str {
+    /// A custom event, its name may be built at runtime, e.g. `format!("job://{id}/progress")`.
+    ///
+    /// A name with other characters than alphanumeric ones, `-`, `/`, `:` and `_` is reported as
+    /// [`UseListenError::Event`] by the functions it is passed to.
+    pub fn custom(name: impl Into<Cow<'static, str>>) -> Self {
+        EventType::Custom(name.into())
+    }
+
+    /// The name sent to Tauri, an error for a custom name Tauri would reject
+    pub(crate) fn name(&self) -> Result<Cow<'static, str>, UseListenError> {
+        let builtin = |name| Ok(Cow::Borrowed(name));
+
         match self {
-            EventType::WindowResized => "tauri://resize",
-            EventType::WindowMoved => "tauri://move",
-            EventType::WindowCloseRequested => "tauri://close-requested",
-            EventType::WindowDestroyed => "tauri://destroyed",
-            EventType::WindowFocus => "tauri://focus",
-            EventType::WindowBlur => "tauri://blur",
-            EventType::WindowScaleFactorChanged => "tauri://scale-change",
-            EventType::WindowThemeChanged => "tauri://theme-changed",
-            EventType::WindowCreated => "tauri://window-created",
-            EventType::WebViewCreated => "tauri://webview-created",
-            EventType::DragEnter => "tauri://drag-enter",
-            EventType::DragOver => "tauri://drag-over",
-            EventType::DragDrop => "tauri://drag-drop",
-            EventType::DragLeave => "tauri://drag-leave",
-            EventType::Custom(s) => {
-                if  s.chars()
-                    .all(|c| c.is_alphabetic() || c == '-' || c == '/' || c == ':' || c == '_') {
-                        s
-                    } else {
-                        panic!("Event name must include only alphanumeric characters, `-`, `/`, `:` and `_`.")
-                    }
-            },
+            EventType::WindowResized => builtin("tauri://resize"),
+            EventType::WindowMoved => builtin("tauri://move"),
+            EventType::WindowCloseRequested => builtin("tauri://close-requested"),
+            EventType::WindowDestroyed => builtin("tauri://destroyed"),
+            EventType::WindowFocus => builtin("tauri://focus"),
+            EventType::WindowBlur => builtin("tauri://blur"),
+            EventType::WindowScaleFactorChanged => builtin("tauri://scale-change"),
+            EventType::WindowThemeChanged => builtin("tauri://theme-changed"),
+            EventType::WindowCreated => builtin("tauri://window-created"),
+            EventType::WebViewCreated => builtin("tauri://webview-created"),
+            EventType::DragEnter => builtin("tauri://drag-enter"),
+            EventType::DragOver => builtin("tauri://drag-over"),
+            EventType::DragDrop => builtin("tauri://drag-drop"),
+            EventType::DragLeave => builtin("tauri://drag-leave"),
+            EventType::Custom(s) if s.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '/' || c == ':' || c == '_') => {
+                Ok(s.clone())
+            }
+            EventType::Custom(s) => Err(UseListenError::Event(
+                s.clone(),
+                "Event name must include only alphanumeric characters, `-`, `/`, `:` and `_`.".to_string(),
+            )),
         }
     }
 }
@@ -246,9 +423,10 @@ pub enum EventTarget {
     WebviewWindow(&'static str)
 }
 
+/// An event as delivered by Tauri
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 #[serde(rename_all = "camelCase")]
-struct Event<T> {
+pub struct Event<T> {
     pub event: String,
     pub id: u32,
     pub payload: T,
@@ -269,7 +447,7 @@ where
 
 #[derive(Clone, Debug)]
 pub enum UseListenError {
-    Event(&'static str, String),
+    Event(Cow<'static, str>, String),
     Serialize(String),
     Deserialize(String),
 }
@@ -287,7 +465,7 @@ impl fmt::Display for UseListenError {
 #[wasm_bindgen]
 extern "C" {
     #[wasm_bindgen(catch, js_namespace = ["window", "__TAURI__", "event"])]
-    async fn listen(
+    pub(crate) async fn listen(
         event: &str,
         closure: &Closure<dyn Fn(JsValue)>,
         option: JsValue,
@@ -0,0 +1,150 @@
+use std::{collections::VecDeque, sync::Arc};
+use reactive_graph::{
+    owner::LocalStorage,
+    signal::signal,
+    traits::Update as _,
+    wrappers::read::Signal,
+};
+use serde::de::DeserializeOwned;
+
+use crate::{listen, use_listen_inner, EventTarget, EventType, UseListenError, UseListenReturn};
+
+/// `use_listen` keeping every payload received, up to the capacity of the config.
+///
+/// Unlike `data` of `use_listen`, no payload is lost when several events arrive between renders.
+///
+/// ```rust,ignore
+/// #[component]
+/// pub fn Demo() -> impl IntoView {
+///     let UseListenBufferedReturn {
+///         buffer: lines,
+///         open,
+///         drain,
+///         ..
+///     } = use_listen_buffered::<String>(
+///         EventType::custom("app://log"),
+///         UseListenBufferedConfig::default().capacity(500),
+///     );
+///
+///     view!{
+///         ...
+///         <For each=move || lines.get() key=|line| line.clone() let:line>
+///             <p>{line}</p>
+///         </For>
+///         <button on:click=move |_| { drain(); }>clear</button>
+///         ...
+///     }
+/// }
+/// ```
+pub fn use_listen_buffered<T>(
+    event: impl Into<Signal<EventType>>,
+    config: UseListenBufferedConfig,
+) -> UseListenBufferedReturn<
+    T,
+    impl Fn() + Clone + Send + Sync + 'static,
+    impl Fn() + Clone + Send + Sync + 'static,
+>
+where
+    T: DeserializeOwned + Clone + PartialEq + Send + Sync + 'static,
+{
+    let UseListenBufferedConfig { capacity, overflow, target } = config;
+
+    let (buffer, set_buffer) = signal(VecDeque::<T>::new());
+
+    let UseListenReturn {
+        event_id,
+        error,
+        open,
+        close,
+        ..
+    } = use_listen_inner::<T, _, _>(event.into(), listen, target, move |payload: &T| {
+        set_buffer.update(|buffer| {
+            if buffer.len() >= capacity {
+                match overflow {
+                    BufferOverflow::DropOldest if capacity > 0 => {
+                        buffer.pop_front();
+                    }
+                    _ => return,
+                }
+            }
+            buffer.push_back(payload.clone());
+        });
+    });
+
+    UseListenBufferedReturn {
+        buffer: buffer.into(),
+        event_id,
+        error,
+        open,
+        close,
+        drain: Arc::new(move || {
+            set_buffer
+                .try_update(std::mem::take)
+                .map(Vec::from)
+                .unwrap_or_default()
+        }),
+    }
+}
+
+/// Options for [`use_listen_buffered`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UseListenBufferedConfig {
+    /// Maximum number of payloads kept, 100 by default
+    pub capacity: usize,
+    /// What happens to a payload arriving while the buffer is full
+    pub overflow: BufferOverflow,
+    pub target: EventTarget,
+}
+
+impl Default for UseListenBufferedConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 100,
+            overflow: BufferOverflow::default(),
+            target: EventTarget::default(),
+        }
+    }
+}
+
+impl UseListenBufferedConfig {
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    pub fn overflow(mut self, overflow: BufferOverflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    pub fn target(mut self, target: EventTarget) -> Self {
+        self.target = target;
+        self
+    }
+}
+
+/// How [`use_listen_buffered`] makes room once its buffer is full
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BufferOverflow {
+    /// Evicts the oldest payload to keep the new one
+    #[default]
+    DropOldest,
+    /// Ignores the new payload
+    DropNewest,
+}
+
+pub struct UseListenBufferedReturn<T, OpenFn, CloseFn>
+where
+    T: DeserializeOwned + Clone + Send + Sync + 'static,
+    OpenFn: Fn() + Clone + Send + Sync + 'static,
+    CloseFn: Fn() + Clone + Send + Sync + 'static,
+{
+    /// Payloads received since the last drain, oldest first
+    pub buffer: Signal<VecDeque<T>>,
+    pub event_id: Signal<Option<u32>>,
+    pub error: Signal<Option<UseListenError>, LocalStorage>,
+    pub open: OpenFn,
+    pub close: CloseFn,
+    /// Empties the buffer, returning its payloads oldest first
+    pub drain: Arc<dyn Fn() -> Vec<T> + Send + Sync>,
+}
@@ -0,0 +1,208 @@
+use std::sync::Arc;
+use reactive_graph::{
+    effect::Effect,
+    owner::StoredValue,
+    signal::{signal, RwSignal},
+    traits::{Get as _, GetUntracked as _, GetValue as _, Set as _, Update as _, UpdateUntracked as _, WithUntracked as _},
+};
+
+use crate::{
+    cached_data, invalidate, invoke_typed, query_key, use_invoke_inner, InvokePolicy, UseInvokeConfig,
+    UseTauriError, UseTauriWithReturn,
+};
+
+/// `use_invoke_with_args` for commands changing data read by [`use_query`](crate::use_query).
+///
+/// Before every call the optimistic updates of the config are applied, and rolled back if the
+/// call fails. A rollback is skipped when the data changed since its update, e.g. by an
+/// overlapping mutation or a refetch, as restoring the whole value would undo that change too.
+/// Once the command ran, the queries listed in [`UseMutationConfig::invalidates`] are
+/// invalidated, even if the component was unmounted meanwhile.
+///
+/// Calls are queued ([`InvokePolicy::Queue`]), so every failure is reported in `error`,
+/// see [`use_mutation_with_config`] for another policy.
+///
+/// ```rust,ignore
+/// #[component]
+/// pub fn Demo() -> impl IntoView {
+///     let UseTauriWithReturn {
+///         trigger: delete,
+///         ..
+///     } = use_mutation::<IdWrapper, ()>(
+///         "delete_data",
+///         UseMutationConfig::default()
+///             .invalidates("list_data")
+///             .optimistic_query("list_data", (), |demos: &mut Option<Vec<Demo>>, id: &IdWrapper| {
+///                 if let Some(demos) = demos {
+///                     demos.retain(|demo| demo.id != id.id);
+///                 }
+///             }),
+///     );
+///
+///     view!{
+///         ...
+///         <button on:click=move |_| delete.set(Some(IdWrapper::new(id)))>delete data</button>
+///         ...
+///     }
+/// }
+/// ```
+pub fn use_mutation<Args, T>(
+    cmd: &'static str,
+    config: UseMutationConfig<Args>,
+) -> UseTauriWithReturn<Args, T>
+where
+    Args: serde::Serialize + Clone + Send + Sync + 'static,
+    T: serde::de::DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    use_mutation_with_config(cmd, config, UseInvokeConfig::default().policy(InvokePolicy::Queue))
+}
+
+/// `use_mutation` with the configuration of [`use_invoke_with_config`](crate::use_invoke_with_config).
+///
+/// A policy discarding results, e.g. [`InvokePolicy::LatestWins`], also discards the failures of
+/// superseded calls, leaving them unreported.
+pub fn use_mutation_with_config<Args, T>(
+    cmd: &'static str,
+    config: UseMutationConfig<Args>,
+    invoke_config: UseInvokeConfig<T>,
+) -> UseTauriWithReturn<Args, T>
+where
+    Args: serde::Serialize + Clone + Send + Sync + 'static,
+    T: serde::de::DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    let UseMutationConfig { invalidates, optimistic } = config;
+    let invalidates = StoredValue::new(invalidates);
+    let optimistic = StoredValue::new(optimistic);
+
+    let (args, set_args) = signal(None::<Args>);
+
+    let ret = use_invoke_inner(cmd, invoke_config, move |cmd, args: Args, options: ()| {
+        let rollbacks = optimistic
+            .try_get_value()
+            .unwrap_or_default()
+            .iter()
+            .map(|update| update(&args))
+            .collect::<Vec<_>>();
+        let invalidates = invalidates.try_get_value().unwrap_or_default();
+
+        async move {
+            let result = invoke_typed::<Args, (), T>(cmd, args, options).await;
+            match &result {
+                // the command ran even if its result couldn't be read
+                Ok(_) | Err(UseTauriError::Deserialize(_)) => {
+                    for cmd in invalidates {
+                        invalidate(cmd);
+                    }
+                }
+                Err(_) => {
+                    for rollback in rollbacks.into_iter().rev() {
+                        rollback();
+                    }
+                }
+            }
+            result
+        }
+    });
+    let trigger = ret.trigger;
+
+    Effect::new(move || {
+        if let Some(args) = args.get() {
+            trigger.set(Some((args, ())));
+            set_args.update_untracked(|v| *v = None);
+        }
+    });
+
+    ret.with_trigger(set_args, |args| (args, ()), |(args, ())| args.clone())
+}
+
+type Rollback = Box<dyn FnOnce()>;
+type OptimisticUpdate<Args> = Arc<dyn Fn(&Args) -> Rollback + Send + Sync>;
+
+/// Options for [`use_mutation`]
+pub struct UseMutationConfig<Args> {
+    /// Commands whose cached queries are invalidated once the command ran,
+    /// i.e. after a successful call or one whose result couldn't be deserialized
+    pub invalidates: Vec<&'static str>,
+    optimistic: Vec<OptimisticUpdate<Args>>,
+}
+
+impl<Args> Default for UseMutationConfig<Args> {
+    fn default() -> Self {
+        Self {
+            invalidates: Vec::new(),
+            optimistic: Vec::new(),
+        }
+    }
+}
+
+impl<Args> Clone for UseMutationConfig<Args> {
+    fn clone(&self) -> Self {
+        Self {
+            invalidates: self.invalidates.clone(),
+            optimistic: self.optimistic.clone(),
+        }
+    }
+}
+
+impl<Args> UseMutationConfig<Args>
+where
+    Args: 'static,
+{
+    pub fn invalidates(mut self, cmd: &'static str) -> Self {
+        self.invalidates.push(cmd);
+        self
+    }
+
+    /// Updates `data` before the command is called, restoring its previous value if the call fails
+    /// and `data` wasn't changed since
+    pub fn optimistic<D>(
+        mut self,
+        data: RwSignal<D>,
+        update: impl Fn(&mut D, &Args) + Send + Sync + 'static,
+    ) -> Self
+    where
+        D: Clone + PartialEq + Send + Sync + 'static,
+    {
+        self.optimistic.push(Arc::new(move |args| {
+            let previous = data.get_untracked();
+            data.update(|data| update(data, args));
+            let updated = data.get_untracked();
+            Box::new(move || {
+                if data.try_with_untracked(|data| *data == updated) == Some(true) {
+                    data.set(previous);
+                }
+            })
+        }));
+        self
+    }
+
+    /// Updates the cached result of the query `cmd` with `query_args` before the command is called,
+    /// restoring its previous value if the call fails and the result wasn't changed since.
+    /// Does nothing if the query isn't cached.
+    pub fn optimistic_query<QArgs, D>(
+        mut self,
+        cmd: &'static str,
+        query_args: QArgs,
+        update: impl Fn(&mut Option<D>, &Args) + Send + Sync + 'static,
+    ) -> Self
+    where
+        QArgs: serde::Serialize + Send + Sync + 'static,
+        D: Clone + PartialEq + Send + Sync + 'static,
+    {
+        self.optimistic.push(Arc::new(move |args| {
+            let Some(data) = query_key(cmd, &query_args).ok().and_then(|key| cached_data::<D>(&key)) else {
+                return Box::new(|| {});
+            };
+
+            let previous = data.get_untracked();
+            data.update(|data| update(data, args));
+            let updated = data.get_untracked();
+            Box::new(move || {
+                if data.with_untracked(|data| *data == updated) {
+                    data.set(previous);
+                }
+            })
+        }));
+        self
+    }
+}
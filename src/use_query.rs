@@ -0,0 +1,405 @@
+use std::{
+    any::{Any, TypeId},
+    cell::RefCell,
+    collections::HashMap,
+    rc::Rc,
+    sync::{atomic::{AtomicU64, Ordering}, Arc},
+    time::Duration,
+};
+use reactive_graph::{
+    effect::Effect,
+    owner::{on_cleanup, LocalStorage, StoredValue},
+    signal::{signal, ArcRwSignal, WriteSignal},
+    traits::{Get as _, GetUntracked as _, GetValue as _, Set as _, SetValue as _, UpdateUntracked as _},
+    wrappers::read::Signal,
+};
+
+use crate::{invoke_typed, UseTauriError};
+
+/// Query-style `use_invoke_with_args` for pure reads: results are cached by command name and args,
+/// shared by every component querying the same key and only re-fetched once they are older than
+/// [`UseQueryConfig::ttl`] or have been invalidated.
+///
+/// Unlike the other hooks it isn't built on [`use_invoke_with_config`](crate::use_invoke_with_config):
+/// a cached result is fetched once for every hook sharing its key and outlives the component that
+/// started the fetch, while the state of the invoke hooks belongs to a single component. There is
+/// hence no retry, timeout, `started_at` or `finished_at`, `updated_at` being the time of the cached result.
+///
+/// ```rust,ignore
+/// #[component]
+/// pub fn Demo() -> impl IntoView {
+///     let UseQueryReturn {
+///         data: demos,
+///         trigger: list,
+///         ..
+///     } = use_query::<(), Vec<Demo>>("list_data");
+///
+///     // e.g. after creating a demo somewhere else
+///     let on_created = move || invalidate("list_data");
+///
+///     view!{
+///         ...
+///         <button on:click=move |_| list.set(Some(()))>list data</button>
+///         ...
+///     }
+/// }
+/// ```
+pub fn use_query<Args, T>(cmd: &'static str) -> UseQueryReturn<Args, T>
+where
+    Args: serde::Serialize + Clone + Send + Sync + 'static,
+    T: serde::de::DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    use_query_with_config(cmd, UseQueryConfig::default())
+}
+
+/// `use_query` with extra configuration, e.g. how long results stay fresh
+pub fn use_query_with_config<Args, T>(
+    cmd: &'static str,
+    config: UseQueryConfig,
+) -> UseQueryReturn<Args, T>
+where
+    Args: serde::Serialize + Clone + Send + Sync + 'static,
+    T: serde::de::DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    let UseQueryConfig { ttl, stale_while_revalidate } = config;
+
+    let (current, set_current) = signal(None::<QueryState<T>>);
+    // `updated_at` of the stale result hidden until it is revalidated
+    let (hidden, set_hidden) = signal(None::<Option<f64>>);
+    let (key_error, set_key_error) = signal(None::<UseTauriError>);
+    let (trigger, set_trigger) = signal(None::<Args>);
+    let key = StoredValue::new(None::<String>);
+
+    Effect::new(move || {
+        if let Some(args) = trigger.get() {
+            set_trigger.update_untracked(|v| *v = None);
+
+            let new_key = match query_key(cmd, &args) {
+                Ok(new_key) => new_key,
+                Err(err) => {
+                    set_key_error.set(Some(err));
+                    return;
+                }
+            };
+            set_key_error.set(None);
+
+            let state = match current.get_untracked() {
+                Some(state) if key.get_value().as_deref() == Some(new_key.as_str()) => state,
+                _ => {
+                    if let Some(old_key) = key.get_value() {
+                        unsubscribe::<T>(&old_key);
+                    }
+                    let state = subscribe::<Args, T>(cmd, &new_key, args, ttl);
+                    key.set_value(Some(new_key));
+                    state
+                }
+            };
+
+            let updated_at = state.updated_at.get_untracked();
+            let is_stale = updated_at.is_none_or(|at| js_sys::Date::now() - at >= ttl.as_millis() as f64);
+
+            if is_stale && !stale_while_revalidate {
+                set_hidden.set(Some(updated_at));
+            } else {
+                set_hidden.set(None);
+            }
+
+            if is_stale && !state.fetching.get_untracked() {
+                (state.refetch)();
+            }
+
+            set_current.set(Some(state));
+        }
+    });
+
+    on_cleanup(move || {
+        if let Some(key) = key.try_get_value().flatten() {
+            unsubscribe::<T>(&key);
+        }
+    });
+
+    UseQueryReturn {
+        data: Signal::derive(move || {
+            let state = current.get()?;
+            let updated_at = state.updated_at.get();
+            match hidden.get() {
+                Some(at) if at == updated_at => None,
+                // invalidated since the trigger
+                _ if updated_at.is_none() && !stale_while_revalidate => None,
+                _ => state.data.get(),
+            }
+        }),
+        error: Signal::derive_local(move || {
+            key_error.get().or_else(|| current.get().and_then(|state| state.error.get()))
+        }),
+        pending: Signal::derive(move || current.get().is_some_and(|state| state.fetching.get())),
+        updated_at: Signal::derive(move || current.get().and_then(|state| state.updated_at.get())),
+        trigger: set_trigger,
+    }
+}
+
+/// Marks every cached result of `cmd` as stale, re-fetching the ones still in use
+pub fn invalidate(cmd: &str) {
+    invalidate_where(|entry_cmd| entry_cmd == cmd);
+}
+
+/// Marks every cached result of the commands starting with `prefix` as stale,
+/// re-fetching the ones still in use, e.g. `invalidate_prefix("plugin:fs|")`
+pub fn invalidate_prefix(prefix: &str) {
+    invalidate_where(|entry_cmd| entry_cmd.starts_with(prefix));
+}
+
+fn invalidate_where(matches: impl Fn(&str) -> bool) {
+    let refetches = QUERY_CACHE.with_borrow_mut(|cache| {
+        cache.retain(|_, entry| !matches(entry.cmd) || entry.subscribers > 0);
+        cache
+            .values()
+            .filter(|entry| matches(entry.cmd))
+            .map(|entry| Rc::clone(&entry.invalidate))
+            .collect::<Vec<_>>()
+    });
+
+    for invalidate in refetches {
+        invalidate();
+    }
+}
+
+/// Options for [`use_query_with_config`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UseQueryConfig {
+    /// How long a cached result is considered fresh, defaults to zero (always revalidated)
+    pub ttl: Duration,
+    /// Keep showing a stale result while it is re-fetched, enabled by default.
+    /// When disabled, `data` is `None` from the moment the result expired or was invalidated until
+    /// it is fetched again.
+    pub stale_while_revalidate: bool,
+}
+
+impl Default for UseQueryConfig {
+    fn default() -> Self {
+        Self {
+            ttl: Duration::ZERO,
+            stale_while_revalidate: true,
+        }
+    }
+}
+
+impl UseQueryConfig {
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    pub fn stale_while_revalidate(mut self, stale_while_revalidate: bool) -> Self {
+        self.stale_while_revalidate = stale_while_revalidate;
+        self
+    }
+}
+
+pub struct UseQueryReturn<Args, T>
+where
+    Args: serde::Serialize + Clone + 'static,
+    T: serde::de::DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    pub data: Signal<Option<T>>,
+    pub error: Signal<Option<UseTauriError>, LocalStorage>,
+    /// `true` while the current key is being fetched
+    pub pending: Signal<bool>,
+    /// Timestamp (ms since epoch) of the cached result
+    pub updated_at: Signal<Option<f64>>,
+    pub trigger: WriteSignal<Option<Args>>,
+}
+
+/// Shared state of a cached query, one per key
+struct QueryState<T> {
+    data: ArcRwSignal<Option<T>>,
+    error: ArcRwSignal<Option<UseTauriError>>,
+    fetching: ArcRwSignal<bool>,
+    updated_at: ArcRwSignal<Option<f64>>,
+    /// bumped by every invalidation, so a fetch started before it doesn't count as fresh
+    invalidations: Arc<AtomicU64>,
+    refetch: Arc<dyn Fn() + Send + Sync>,
+}
+
+impl<T> Clone for QueryState<T> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+            error: self.error.clone(),
+            fetching: self.fetching.clone(),
+            updated_at: self.updated_at.clone(),
+            invalidations: Arc::clone(&self.invalidations),
+            refetch: Arc::clone(&self.refetch),
+        }
+    }
+}
+
+struct CacheEntry {
+    cmd: &'static str,
+    /// `QueryState<T>` of the `T` in the entry's key
+    state: Rc<dyn Any>,
+    subscribers: usize,
+    /// Longest ttl of the hooks subscribed so far, the entry is kept that long once unused
+    ttl: Duration,
+    updated_at: Rc<dyn Fn() -> Option<f64>>,
+    invalidate: Rc<dyn Fn()>,
+}
+
+impl CacheEntry {
+    fn is_expired(&self, now: f64) -> bool {
+        (self.updated_at)().is_none_or(|at| now - at >= self.ttl.as_millis() as f64)
+    }
+}
+
+thread_local! {
+    /// Keyed by query key and result type, so the same command read as different types doesn't collide
+    static QUERY_CACHE: RefCell<HashMap<(String, TypeId), CacheEntry>> = RefCell::new(HashMap::new());
+}
+
+pub(crate) fn query_key<Args>(cmd: &str, args: &Args) -> Result<String, UseTauriError>
+where
+    Args: serde::Serialize,
+{
+    let serializer = serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true);
+    let args = args
+        .serialize(&serializer)
+        .map_err(|err| UseTauriError::Serialize(err.to_string()))?;
+    let args = js_sys::JSON::stringify(&args)
+        .ok()
+        .and_then(|json| json.as_string())
+        .unwrap_or_default();
+    Ok(format!("{}:{}", cmd, args))
+}
+
+/// Returns the shared state of `key`, creating it if needed, and counts one more subscriber
+fn subscribe<Args, T>(cmd: &'static str, key: &str, args: Args, ttl: Duration) -> QueryState<T>
+where
+    Args: serde::Serialize + Clone + Send + Sync + 'static,
+    T: serde::de::DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    QUERY_CACHE.with_borrow_mut(|cache| {
+        evict_expired(cache);
+
+        let key = (key.to_string(), TypeId::of::<T>());
+        if let Some(entry) = cache.get_mut(&key)
+            && let Some(state) = entry.state.downcast_ref::<QueryState<T>>()
+        {
+            entry.subscribers += 1;
+            entry.ttl = entry.ttl.max(ttl);
+            return state.clone();
+        }
+
+        let state = new_query_state::<Args, T>(cmd, args);
+        let updated_at = {
+            let updated_at = state.updated_at.clone();
+            Rc::new(move || updated_at.get_untracked())
+        };
+        let invalidate = {
+            let state = state.clone();
+            Rc::new(move || {
+                state.invalidations.fetch_add(1, Ordering::Relaxed);
+                state.updated_at.set(None);
+                // otherwise the running fetch goes again once it settles
+                if !state.fetching.get_untracked() {
+                    (state.refetch)();
+                }
+            })
+        };
+        cache.insert(key, CacheEntry {
+            cmd,
+            state: Rc::new(state.clone()),
+            subscribers: 1,
+            ttl,
+            updated_at,
+            invalidate,
+        });
+        state
+    })
+}
+
+/// The cached data of `key`, if it is cached with type `T`
+pub(crate) fn cached_data<T>(key: &str) -> Option<ArcRwSignal<Option<T>>>
+where
+    T: 'static,
+{
+    QUERY_CACHE.with_borrow(|cache| {
+        let entry = cache.get(&(key.to_string(), TypeId::of::<T>()))?;
+        let state = entry.state.downcast_ref::<QueryState<T>>()?;
+        Some(state.data.clone())
+    })
+}
+
+fn unsubscribe<T>(key: &str)
+where
+    T: 'static,
+{
+    QUERY_CACHE.with_borrow_mut(|cache| {
+        if let Some(entry) = cache.get_mut(&(key.to_string(), TypeId::of::<T>())) {
+            entry.subscribers = entry.subscribers.saturating_sub(1);
+        }
+        evict_expired(cache);
+    });
+}
+
+/// Drops the entries nobody subscribes to anymore once their result is past the ttl
+fn evict_expired(cache: &mut HashMap<(String, TypeId), CacheEntry>) {
+    let now = js_sys::Date::now();
+    cache.retain(|_, entry| entry.subscribers > 0 || !entry.is_expired(now));
+}
+
+fn new_query_state<Args, T>(cmd: &'static str, args: Args) -> QueryState<T>
+where
+    Args: serde::Serialize + Clone + Send + Sync + 'static,
+    T: serde::de::DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    let data = ArcRwSignal::new(None::<T>);
+    let error = ArcRwSignal::new(None::<UseTauriError>);
+    let fetching = ArcRwSignal::new(false);
+    let updated_at = ArcRwSignal::new(None::<f64>);
+    let invalidations = Arc::new(AtomicU64::new(0));
+
+    let refetch = {
+        let (data, error, fetching, updated_at) = (data.clone(), error.clone(), fetching.clone(), updated_at.clone());
+        let invalidations = Arc::clone(&invalidations);
+
+        Arc::new(move || {
+            let (data, error, fetching, updated_at) = (data.clone(), error.clone(), fetching.clone(), updated_at.clone());
+            let invalidations = Arc::clone(&invalidations);
+            let args = args.clone();
+            fetching.set(true);
+
+            // not scoped to a component, the result belongs to the cache
+            wasm_bindgen_futures::spawn_local(async move {
+                loop {
+                    let id = invalidations.load(Ordering::Relaxed);
+                    let result = invoke_typed::<Args, (), T>(cmd, args.clone(), ()).await;
+                    let invalidated = invalidations.load(Ordering::Relaxed) != id;
+
+                    match result {
+                        Ok(value) => {
+                            data.set(Some(value));
+                            error.set(None);
+                            if !invalidated {
+                                updated_at.set(Some(js_sys::Date::now()));
+                            }
+                        }
+                        Err(err) => error.set(Some(err)),
+                    }
+                    if !invalidated {
+                        break;
+                    }
+                }
+                fetching.set(false);
+            });
+        })
+    };
+
+    QueryState {
+        data,
+        error,
+        fetching,
+        updated_at,
+        invalidations,
+        refetch,
+    }
+}
@@ -0,0 +1,71 @@
+use crate::{use_invoke_with_args_and_config, UseInvokeConfig, UseTauriWithReturn};
+
+/// A Tauri command with its name, arguments and output declared once.
+///
+/// Usually implemented with the [`tauri_command!`](crate::tauri_command) macro.
+pub trait TauriCommand {
+    /// The command name passed to `invoke`
+    const NAME: &'static str;
+    type Args: serde::Serialize + Clone + Send + Sync + 'static;
+    type Output: serde::de::DeserializeOwned + Clone + Send + Sync + 'static;
+}
+
+/// Declares marker types implementing [`TauriCommand`].
+///
+/// ```rust,ignore
+/// tauri_command! {
+///     /// Creates a new demo
+///     pub struct CreateDemo("create_demo"): DemoModelWrapper => Demo;
+///     pub struct ListData("list_data"): () => Vec<Demo>;
+/// }
+/// ```
+#[macro_export]
+macro_rules! tauri_command {
+    ($(
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident($cmd:literal): $args:ty => $output:ty;
+    )*) => {$(
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+        $vis struct $name;
+
+        impl $crate::TauriCommand for $name {
+            const NAME: &'static str = $cmd;
+            type Args = $args;
+            type Output = $output;
+        }
+    )*};
+}
+
+/// An alias function of `use_invoke_with_args` for a [`TauriCommand`]
+///
+/// ```rust,ignore
+/// #[component]
+/// pub fn Demo() -> impl IntoView {
+///     let UseTauriWithReturn {
+///         data: created_demo_data,
+///         trigger: create_demo,
+///         ..
+///     } = use_typed_command::<CreateDemo>();
+///
+///     view!{
+///         ...
+///         <button on:click=move |_| create_demo.set(Some(DemoModelWrapper::new(...)))>create</button>
+///         ...
+///     }
+/// }
+/// ```
+pub fn use_typed_command<C>() -> UseTauriWithReturn<C::Args, C::Output>
+where
+    C: TauriCommand,
+{
+    use_typed_command_with_config::<C>(UseInvokeConfig::default())
+}
+
+/// `use_typed_command` with the configuration of [`use_invoke_with_config`](crate::use_invoke_with_config)
+pub fn use_typed_command_with_config<C>(config: UseInvokeConfig<C::Output>) -> UseTauriWithReturn<C::Args, C::Output>
+where
+    C: TauriCommand,
+{
+    use_invoke_with_args_and_config::<C::Args, C::Output>(C::NAME, config)
+}
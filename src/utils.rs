@@ -0,0 +1,142 @@
+use std::{future::poll_fn, pin::pin, task::Poll, time::Duration};
+use reactive_graph::{
+    owner::StoredValue,
+    spawn_local_scoped,
+    traits::{GetValue as _, SetValue as _, UpdateValue as _},
+};
+use wasm_bindgen::prelude::*;
+
+use crate::RateLimit;
+
+/// Resolves after `duration` has passed, using the browser's `setTimeout`.
+pub(crate) async fn sleep(duration: Duration) {
+    let timeout = duration.as_millis().min(i32::MAX as u128) as i32;
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        set_timeout(&resolve, timeout);
+    });
+    let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+}
+
+/// Races `future` against a timer, dropping it and returning `None` once `duration` has passed.
+pub(crate) async fn timeout<F>(duration: Duration, future: F) -> Option<F::Output>
+where
+    F: Future,
+{
+    let mut future = pin!(future);
+    let mut timer = pin!(sleep(duration));
+
+    poll_fn(|cx| {
+        if let Poll::Ready(output) = future.as_mut().poll(cx) {
+            return Poll::Ready(Some(output));
+        }
+        timer.as_mut().poll(cx).map(|_| None)
+    })
+    .await
+}
+
+/// The message of a rejected promise: strings as they are, JS `Error`s by their message
+/// and structured values as JSON.
+pub(crate) fn rejection_message(err: JsValue) -> String {
+    if let Some(err_str) = err.as_string() {
+        return err_str;
+    }
+    // e.g. an IPC failure, which would stringify to `{}`
+    if let Some(err) = err.dyn_ref::<js_sys::Error>() {
+        return String::from(err.message());
+    }
+
+    js_sys::JSON::stringify(&err)
+        .ok()
+        .and_then(|json| json.as_string())
+        .unwrap_or_else(|| "Unknown error".to_string())
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = setTimeout)]
+    fn set_timeout(handler: &js_sys::Function, timeout: i32) -> JsValue;
+}
+
+/// Wraps `f` so bursts of calls are coalesced according to `limit`, calling `f` directly without one.
+/// 
+/// A burst starts with the first call and ends once no call happened for `limit.wait`.
+/// Also returns a function dropping the call held back, if any.
+pub(crate) fn rate_limited<A>(
+    limit: Option<RateLimit>,
+    f: impl Fn(A) + Copy + 'static,
+) -> (impl Fn(A) + Copy + 'static, impl Fn() + Copy + 'static)
+where
+    A: Send + Sync + 'static,
+{
+    let pending = StoredValue::new(None::<A>);
+    let last_call = StoredValue::new(0f64);
+    // start of the burst or time of the last call of `f` in it, `None` outside of a burst
+    let anchor = StoredValue::new(None::<f64>);
+
+    let flush = move || {
+        if let Some(value) = pending.try_update_value(|v| v.take()).flatten() {
+            anchor.set_value(Some(js_sys::Date::now()));
+            f(value);
+        }
+    };
+
+    let cancel = move || {
+        pending.set_value(None);
+    };
+
+    let call = move |value: A| {
+        let Some(RateLimit { wait, leading, trailing, max_wait }) = limit else {
+            f(value);
+            return;
+        };
+
+        let now = js_sys::Date::now();
+        last_call.set_value(now);
+        pending.set_value(Some(value));
+
+        if anchor.get_value().is_some() {
+            return;
+        }
+
+        anchor.set_value(Some(now));
+        if leading {
+            flush();
+        }
+
+        spawn_local_scoped(async move {
+            let wait = wait.as_millis() as f64;
+            // like lodash, a `max_wait` shorter than `wait` would leave nothing to wait for
+            let max_wait = max_wait.map(|max_wait| (max_wait.as_millis() as f64).max(wait));
+
+            loop {
+                // both are gone once the owner is cleaned up
+                let (Some(Some(anchored_at)), Some(called_at)) = (anchor.try_get_value(), last_call.try_get_value()) else {
+                    return;
+                };
+                let quiet_at = called_at + wait;
+                let deadline = max_wait.map_or(quiet_at, |max_wait| quiet_at.min(anchored_at + max_wait));
+
+                sleep(Duration::from_millis((deadline - js_sys::Date::now()).max(0.0) as u64)).await;
+
+                let Some(called_at) = last_call.try_get_value() else {
+                    return;
+                };
+                let now = js_sys::Date::now();
+                if now >= called_at + wait {
+                    if trailing {
+                        flush();
+                    } else {
+                        pending.set_value(None);
+                    }
+                    anchor.set_value(None);
+                    return;
+                }
+                if max_wait.is_some_and(|max_wait| now >= anchored_at + max_wait) {
+                    flush();
+                }
+            }
+        });
+    };
+
+    (call, cancel)
+}
@@ -0,0 +1,825 @@
+#![cfg(target_arch = "wasm32")]
+// run with `cargo test --target wasm32-unknown-unknown` and `wasm-bindgen-test-runner` as the runner
+
+use std::{future::poll_fn, task::Poll, time::Duration};
+use any_spawner::Executor;
+use reactive_graph::{
+    owner::Owner,
+    signal::RwSignal,
+    traits::{GetUntracked as _, Set as _},
+    wrappers::read::Signal,
+};
+use serde::{Serialize, Serializer};
+use tauri_use::*;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+// stands in for `window.__TAURI__.core`, every call stays pending until the test settles it
+#[wasm_bindgen(inline_js = r#"
+const calls = [];
+const channels = [];
+
+class Channel {
+    constructor() {
+        this.id = channels.length;
+        this.onmessage = () => {};
+        channels.push(this);
+    }
+}
+
+export function install_mock() {
+    calls.length = 0;
+    channels.length = 0;
+    globalThis.window = globalThis;
+    globalThis.__TAURI__ = {
+        core: {
+            Channel,
+            invoke: (cmd, args, options) => new Promise((resolve, reject) => {
+                calls.push({ cmd, args: JSON.stringify(args), options: JSON.stringify(options), resolve, reject });
+            }),
+        },
+    };
+}
+
+export function call_count(cmd) { return calls.filter((call) => call.cmd === cmd).length; }
+export function call_args(cmd, i) { return calls.filter((call) => call.cmd === cmd)[i].args; }
+export function call_options(cmd, i) { return calls.filter((call) => call.cmd === cmd)[i].options; }
+export function resolve_call(cmd, i, value) { calls.filter((call) => call.cmd === cmd)[i].resolve(value); }
+export function reject_call(cmd, i, value) { calls.filter((call) => call.cmd === cmd)[i].reject(value); }
+export function channel_send(id, message) { channels[id].onmessage(message); }
+export function sleep_ms(ms) { return new Promise((resolve) => setTimeout(resolve, ms)); }
+"#)]
+extern "C" {
+    fn install_mock();
+    fn call_count(cmd: &str) -> u32;
+    fn call_args(cmd: &str, i: u32) -> Option<String>;
+    fn call_options(cmd: &str, i: u32) -> Option<String>;
+    fn resolve_call(cmd: &str, i: u32, value: JsValue);
+    fn reject_call(cmd: &str, i: u32, value: JsValue);
+    fn channel_send(id: u32, message: JsValue);
+    fn sleep_ms(ms: u32) -> js_sys::Promise;
+}
+
+fn setup() -> Owner {
+    let _ = Executor::init_wasm_bindgen();
+    install_mock();
+
+    let owner = Owner::new();
+    owner.set();
+    owner
+}
+
+async fn sleep(ms: u32) {
+    JsFuture::from(sleep_ms(ms)).await.unwrap();
+}
+
+/// Lets effects run and settled calls be applied
+async fn settle() {
+    sleep(0).await;
+}
+
+fn use_numbers(cmd: &'static str, config: UseInvokeConfig<u32>) -> UseTauriReturn<u32, (), u32> {
+    use_invoke_with_config::<u32, (), u32>(cmd, config)
+}
+
+async fn trigger(ret: &UseTauriReturn<u32, (), u32>, args: u32) {
+    ret.trigger.set(Some((args, ())));
+    settle().await;
+}
+
+#[wasm_bindgen_test]
+async fn latest_wins_drops_out_of_order_results() {
+    let _owner = setup();
+    let ret = use_numbers("latest", UseInvokeConfig::default().policy(InvokePolicy::LatestWins));
+
+    trigger(&ret, 1).await;
+    trigger(&ret, 2).await;
+    assert_eq!(call_count("latest"), 2);
+
+    resolve_call("latest", 1, JsValue::from(20));
+    settle().await;
+    assert_eq!(ret.data.get_untracked(), Some(20));
+    assert!(ret.pending.get_untracked());
+
+    resolve_call("latest", 0, JsValue::from(10));
+    settle().await;
+    assert_eq!(ret.data.get_untracked(), Some(20));
+    assert!(!ret.pending.get_untracked());
+}
+
+#[wasm_bindgen_test]
+async fn parallel_keeps_results_unless_a_newer_one_resolved() {
+    let _owner = setup();
+    let ret = use_numbers("parallel", UseInvokeConfig::default().policy(InvokePolicy::Parallel));
+
+    trigger(&ret, 1).await;
+    trigger(&ret, 2).await;
+    trigger(&ret, 3).await;
+
+    resolve_call("parallel", 0, JsValue::from(10));
+    settle().await;
+    assert_eq!(ret.data.get_untracked(), Some(10));
+
+    resolve_call("parallel", 2, JsValue::from(30));
+    resolve_call("parallel", 1, JsValue::from(20));
+    settle().await;
+    assert_eq!(ret.data.get_untracked(), Some(30));
+    assert!(!ret.pending.get_untracked());
+}
+
+#[wasm_bindgen_test]
+async fn queue_calls_one_after_another() {
+    let _owner = setup();
+    let ret = use_numbers("queue", UseInvokeConfig::default().policy(InvokePolicy::Queue));
+
+    trigger(&ret, 1).await;
+    trigger(&ret, 2).await;
+    assert_eq!(call_count("queue"), 1);
+
+    resolve_call("queue", 0, JsValue::from(10));
+    settle().await;
+    assert_eq!(ret.data.get_untracked(), Some(10));
+    assert_eq!(call_count("queue"), 2);
+    assert_eq!(call_args("queue", 1).as_deref(), Some("2"));
+    assert!(ret.pending.get_untracked());
+
+    resolve_call("queue", 1, JsValue::from(20));
+    settle().await;
+    assert_eq!(ret.data.get_untracked(), Some(20));
+    assert!(!ret.pending.get_untracked());
+}
+
+#[wasm_bindgen_test]
+async fn drop_while_pending_ignores_triggers() {
+    let _owner = setup();
+    let ret = use_numbers("drop", UseInvokeConfig::default().policy(InvokePolicy::DropWhilePending));
+
+    trigger(&ret, 1).await;
+    trigger(&ret, 2).await;
+    assert_eq!(call_count("drop"), 1);
+
+    resolve_call("drop", 0, JsValue::from(10));
+    settle().await;
+    trigger(&ret, 3).await;
+    assert_eq!(call_count("drop"), 2);
+    assert_eq!(call_args("drop", 1).as_deref(), Some("3"));
+}
+
+#[wasm_bindgen_test]
+async fn cleaned_up_owner_ignores_results_in_flight() {
+    let owner = setup();
+    let ret = use_numbers("cleanup", UseInvokeConfig::default());
+
+    trigger(&ret, 1).await;
+    owner.cleanup();
+    resolve_call("cleanup", 0, JsValue::from(10));
+    settle().await;
+}
+
+#[wasm_bindgen_test]
+async fn options_headers_are_passed_as_a_record() {
+    let _owner = setup();
+    let ret = use_invoke_with_options::<InvokeOptions, u32>("headers");
+
+    ret.trigger.set(Some(InvokeOptions::new().header("x-trace-id", "1")));
+    settle().await;
+    assert_eq!(call_args("headers", 0).as_deref(), None);
+    assert_eq!(call_options("headers", 0).as_deref(), Some(r#"{"headers":{"x-trace-id":"1"}}"#));
+}
+
+#[wasm_bindgen_test]
+async fn plugin_options_are_passed_as_args() {
+    let _owner = setup();
+    let save = plugins::dialog::use_save_dialog();
+    let exists = plugins::fs::use_exists();
+
+    save.trigger.set(Some(plugins::dialog::SaveDialogOptions { title: Some("Export".to_string()), ..Default::default() }));
+    exists.trigger.set(Some(plugins::fs::ExistsOptions { base_dir: None }));
+    settle().await;
+
+    let save_args = call_args("plugin:dialog|save", 0).unwrap();
+    assert!(save_args.starts_with(r#"{"options":{"title":"Export","#), "{save_args}");
+    assert_eq!(call_options("plugin:dialog|save", 0).as_deref(), None);
+    assert_eq!(call_args("plugin:fs|exists", 0).as_deref(), Some(r#"{"options":{}}"#));
+    assert_eq!(call_options("plugin:fs|exists", 0).as_deref(), None);
+}
+
+#[wasm_bindgen_test]
+async fn reset_discards_calls_in_flight() {
+    let _owner = setup();
+    let ret = use_numbers("reset", UseInvokeConfig::default());
+
+    trigger(&ret, 1).await;
+    (ret.reset)();
+    resolve_call("reset", 0, JsValue::from(10));
+    settle().await;
+    assert_eq!(ret.data.get_untracked(), None);
+    assert!(!ret.pending.get_untracked());
+}
+
+#[wasm_bindgen_test]
+async fn reset_drops_a_debounced_trigger() {
+    let _owner = setup();
+    let ret = use_invoke_with_args_and_config::<u32, u32>(
+        "reset_debounce",
+        UseInvokeConfig::default().debounce(Duration::from_millis(20)),
+    );
+
+    ret.trigger.set(Some(1));
+    settle().await;
+    (ret.reset)();
+
+    sleep(40).await;
+    assert_eq!(call_count("reset_debounce"), 0);
+}
+
+#[wasm_bindgen_test]
+async fn reset_discards_calls_not_started_yet() {
+    let _owner = setup();
+    let ret = use_numbers("reset_unpolled", UseInvokeConfig::default());
+
+    let call = (ret.execute)(1, ());
+    (ret.reset)();
+    wasm_bindgen_futures::spawn_local(async {
+        settle().await;
+        resolve_call("reset_unpolled", 0, JsValue::from(10));
+    });
+    assert_eq!(call.await.unwrap(), 10);
+    assert_eq!(ret.data.get_untracked(), None);
+}
+
+#[wasm_bindgen_test]
+async fn retry_with_backoff() {
+    let _owner = setup();
+    let ret = use_numbers(
+        "retry",
+        UseInvokeConfig::default().retry(RetryPolicy::new(3).backoff(Backoff::Fixed(Duration::from_millis(20)))),
+    );
+
+    trigger(&ret, 1).await;
+    reject_call("retry", 0, JsValue::from_str("busy"));
+    settle().await;
+    assert_eq!(call_count("retry"), 1);
+
+    sleep(40).await;
+    assert_eq!(call_count("retry"), 2);
+    assert_eq!(ret.attempt.get_untracked(), 2);
+
+    resolve_call("retry", 1, JsValue::from(10));
+    settle().await;
+    assert_eq!(ret.data.get_untracked(), Some(10));
+    assert!(ret.error.get_untracked().is_none());
+}
+
+#[wasm_bindgen_test]
+async fn retry_gives_up_after_max_attempts() {
+    let _owner = setup();
+    let ret = use_numbers(
+        "retry_max",
+        UseInvokeConfig::default().retry(RetryPolicy::new(2).backoff(Backoff::Fixed(Duration::ZERO))),
+    );
+
+    trigger(&ret, 1).await;
+    reject_call("retry_max", 0, JsValue::from_str("busy"));
+    sleep(10).await;
+    reject_call("retry_max", 1, JsValue::from_str("still busy"));
+    sleep(10).await;
+
+    assert_eq!(call_count("retry_max"), 2);
+    assert!(matches!(ret.error.get_untracked(), Some(UseTauriError::Command(_, err)) if err == "still busy"));
+}
+
+#[wasm_bindgen_test]
+async fn timeout_ignores_late_results() {
+    let _owner = setup();
+    let ret = use_numbers("timeout", UseInvokeConfig::default().timeout(Duration::from_millis(20)));
+
+    trigger(&ret, 1).await;
+    sleep(40).await;
+    assert!(matches!(ret.error.get_untracked(), Some(UseTauriError::Timeout { .. })));
+    assert!(!ret.pending.get_untracked());
+
+    resolve_call("timeout", 0, JsValue::from(10));
+    settle().await;
+    assert_eq!(ret.data.get_untracked(), None);
+}
+
+#[wasm_bindgen_test]
+async fn execute_with_timeout_overrides_the_hook_timeout() {
+    let _owner = setup();
+    let ret = use_numbers("timeout_override", UseInvokeConfig::default().timeout(Duration::from_secs(60)));
+
+    let result = (ret.execute_with_timeout)(1, (), Duration::from_millis(20)).await;
+    assert!(matches!(result, Err(UseTauriError::Timeout { after, .. }) if after == Duration::from_millis(20)));
+    assert!(matches!(ret.error.get_untracked(), Some(UseTauriError::Timeout { .. })));
+    assert!(!ret.pending.get_untracked());
+}
+
+#[wasm_bindgen_test]
+async fn js_error_rejection_keeps_its_message() {
+    let _owner = setup();
+    let ret = use_numbers("js_error", UseInvokeConfig::default());
+
+    trigger(&ret, 1).await;
+    reject_call("js_error", 0, js_sys::Error::new("ipc failed").into());
+    settle().await;
+    assert!(matches!(ret.error.get_untracked(), Some(UseTauriError::Command(_, err)) if err == "ipc failed"));
+}
+
+#[wasm_bindgen_test]
+async fn dropped_execute_is_not_pending() {
+    let _owner = setup();
+    let ret = use_numbers("execute", UseInvokeConfig::default());
+
+    drop((ret.execute)(1, ()));
+    assert!(!ret.pending.get_untracked());
+    assert_eq!(call_count("execute"), 0);
+
+    // started, then dropped before it resolves
+    let mut call = (ret.execute)(2, ());
+    poll_fn(|cx| {
+        let _ = call.as_mut().poll(cx);
+        Poll::Ready(())
+    })
+    .await;
+    assert!(ret.pending.get_untracked());
+    assert_eq!(call_count("execute"), 1);
+
+    drop(call);
+    assert!(!ret.pending.get_untracked());
+}
+
+#[wasm_bindgen_test]
+async fn execute_resolves_after_the_owner_is_cleaned_up() {
+    let owner = setup();
+    let ret = use_numbers("execute_cleanup", UseInvokeConfig::default());
+
+    let call = (ret.execute)(1, ());
+    owner.cleanup();
+    wasm_bindgen_futures::spawn_local(async {
+        settle().await;
+        resolve_call("execute_cleanup", 0, JsValue::from(10));
+    });
+    assert_eq!(call.await.unwrap(), 10);
+}
+
+#[wasm_bindgen_test]
+async fn history_through_alias_hooks() {
+    let _owner = setup();
+    let ret = use_invoke_with_args_and_config::<u32, u32>("history", UseInvokeConfig::default().history(2));
+
+    for (i, args) in [1, 2, 3].into_iter().enumerate() {
+        ret.trigger.set(Some(args));
+        settle().await;
+        resolve_call("history", i as u32, JsValue::from(args * 10));
+        settle().await;
+    }
+
+    let history = ret.history.get_untracked();
+    assert_eq!(history.iter().map(|record| (record.args, record.data)).collect::<Vec<_>>(), vec![(2, 20), (3, 30)]);
+    assert_eq!(ret.previous.get_untracked(), Some(20));
+}
+
+#[wasm_bindgen_test]
+async fn watch_calls_on_changes_and_immediately_if_asked() {
+    let _owner = setup();
+    let source = RwSignal::new(1u32);
+    let lazy = use_invoke_watch::<u32, u32>("watch_lazy", source, UseInvokeWatchConfig::default());
+    let eager = use_invoke_watch::<u32, u32>("watch_eager", source, UseInvokeWatchConfig::default().immediate(true));
+
+    settle().await;
+    assert_eq!(call_count("watch_lazy"), 0);
+    assert_eq!(call_count("watch_eager"), 1);
+    assert_eq!(call_args("watch_eager", 0).as_deref(), Some("1"));
+
+    source.set(2);
+    settle().await;
+    assert_eq!(call_count("watch_lazy"), 1);
+    assert_eq!(call_args("watch_lazy", 0).as_deref(), Some("2"));
+    assert_eq!(call_count("watch_eager"), 2);
+
+    resolve_call("watch_lazy", 0, JsValue::from(20));
+    settle().await;
+    assert_eq!(lazy.data.get_untracked(), Some(20));
+    assert!(eager.data.get_untracked().is_none());
+}
+
+#[wasm_bindgen_test]
+async fn watch_debounces_bursts_of_changes() {
+    let _owner = setup();
+    let source = RwSignal::new(0u32);
+    let _ret = use_invoke_watch::<u32, u32>(
+        "watch_debounce",
+        source,
+        UseInvokeWatchConfig::default().debounce(Duration::from_millis(40)),
+    );
+
+    for value in 1..=3 {
+        source.set(value);
+        sleep(5).await;
+    }
+    assert_eq!(call_count("watch_debounce"), 0);
+
+    sleep(80).await;
+    assert_eq!(call_count("watch_debounce"), 1);
+    assert_eq!(call_args("watch_debounce", 0).as_deref(), Some("3"));
+}
+
+#[wasm_bindgen_test]
+async fn watch_drops_superseded_results() {
+    let _owner = setup();
+    let source = RwSignal::new(0u32);
+    let ret = use_invoke_watch_with_config::<u32, u32>(
+        "watch_latest",
+        source,
+        UseInvokeWatchConfig::default(),
+        UseInvokeConfig::default().history(2),
+    );
+
+    source.set(1);
+    settle().await;
+    source.set(2);
+    settle().await;
+    assert_eq!(call_count("watch_latest"), 2);
+
+    resolve_call("watch_latest", 1, JsValue::from(20));
+    resolve_call("watch_latest", 0, JsValue::from(10));
+    settle().await;
+    assert_eq!(ret.data.get_untracked(), Some(20));
+    assert_eq!(ret.history.get_untracked().len(), 1);
+}
+
+#[wasm_bindgen_test]
+async fn debounce_calls_once_with_the_latest_trigger() {
+    let _owner = setup();
+    let ret = use_invoke_with_args_and_config::<u32, u32>(
+        "debounce",
+        UseInvokeConfig::default().debounce(Duration::from_millis(40)),
+    );
+
+    for args in 1..=3 {
+        ret.trigger.set(Some(args));
+        sleep(5).await;
+    }
+    assert_eq!(call_count("debounce"), 0);
+
+    sleep(80).await;
+    assert_eq!(call_count("debounce"), 1);
+    assert_eq!(call_args("debounce", 0).as_deref(), Some("3"));
+}
+
+#[wasm_bindgen_test]
+async fn debounce_with_max_wait_calls_during_a_burst() {
+    let _owner = setup();
+    let ret = use_invoke_with_args_and_config::<u32, u32>(
+        "max_wait",
+        UseInvokeConfig::default().rate_limit(RateLimit::debounce(Duration::from_millis(40)).max_wait(Duration::from_millis(60))),
+    );
+
+    // triggers keep coming faster than `wait`, only `max_wait` lets calls through
+    for args in 1..=12 {
+        ret.trigger.set(Some(args));
+        sleep(15).await;
+    }
+    let during_burst = call_count("max_wait");
+    assert!(during_burst >= 2, "{during_burst} calls during the burst");
+
+    // the trailing call only happens if a trigger came after the last forced call
+    sleep(80).await;
+    let calls = call_count("max_wait");
+    assert!(calls <= during_burst + 1);
+    assert_eq!(call_args("max_wait", calls - 1).as_deref(), Some("12"));
+}
+
+#[wasm_bindgen_test]
+async fn debounce_drops_the_trigger_when_the_owner_is_cleaned_up() {
+    let owner = setup();
+    let ret = use_invoke_with_args_and_config::<u32, u32>(
+        "debounce_cleanup",
+        UseInvokeConfig::default().debounce(Duration::from_millis(20)),
+    );
+
+    ret.trigger.set(Some(1));
+    settle().await;
+    owner.cleanup();
+
+    sleep(40).await;
+    assert_eq!(call_count("debounce_cleanup"), 0);
+}
+
+#[wasm_bindgen_test]
+async fn max_wait_shorter_than_wait_acts_like_wait() {
+    let _owner = setup();
+    let ret = use_invoke_with_args_and_config::<u32, u32>(
+        "short_max_wait",
+        UseInvokeConfig::default().rate_limit(RateLimit::debounce(Duration::from_millis(40)).max_wait(Duration::from_millis(5))),
+    );
+
+    for args in 1..=6 {
+        ret.trigger.set(Some(args));
+        sleep(15).await;
+    }
+    // forced at most every 40ms rather than every 5ms
+    let during_burst = call_count("short_max_wait");
+    assert!((1..=3).contains(&during_burst), "{during_burst} calls during the burst");
+}
+
+#[wasm_bindgen_test]
+async fn throttle_calls_on_both_edges() {
+    let _owner = setup();
+    let ret = use_invoke_with_args_and_config::<u32, u32>(
+        "throttle",
+        UseInvokeConfig::default().throttle(Duration::from_millis(50)),
+    );
+
+    ret.trigger.set(Some(1));
+    settle().await;
+    assert_eq!(call_count("throttle"), 1);
+
+    ret.trigger.set(Some(2));
+    settle().await;
+    ret.trigger.set(Some(3));
+    settle().await;
+    assert_eq!(call_count("throttle"), 1);
+
+    sleep(80).await;
+    assert_eq!(call_count("throttle"), 2);
+    assert_eq!(call_args("throttle", 1).as_deref(), Some("3"));
+}
+
+#[wasm_bindgen_test]
+async fn bytes_with_config_retries() {
+    let _owner = setup();
+    let ret = use_invoke_bytes_with_config::<(), ()>(
+        "bytes",
+        UseInvokeConfig::default().retry(RetryPolicy::new(2).backoff(Backoff::Fixed(Duration::ZERO))),
+    );
+
+    ret.trigger.set(Some(((), ())));
+    settle().await;
+    reject_call("bytes", 0, JsValue::from_str("busy"));
+    sleep(10).await;
+    resolve_call("bytes", 1, js_sys::Uint8Array::from(&[1u8, 2, 3][..]).into());
+    settle().await;
+    assert_eq!(ret.data.get_untracked(), Some(vec![1, 2, 3]));
+}
+
+#[wasm_bindgen_test]
+async fn infinite_ignores_pages_after_the_owner_is_cleaned_up() {
+    let owner = setup();
+    let _ret = use_invoke_infinite::<(), u32, u32>("pages", Signal::stored(()), 10);
+
+    settle().await;
+    assert_eq!(call_count("pages"), 1);
+
+    owner.cleanup();
+    resolve_call("pages", 0, js_sys::JSON::parse(r#"{"items":[1,2],"next_cursor":2}"#).unwrap());
+    settle().await;
+}
+
+#[wasm_bindgen_test]
+async fn channel_keeps_messages_in_order() {
+    let _owner = setup();
+    let ret = use_channel_with_config::<u32>(UseChannelConfig::default().accumulate(true));
+
+    channel_send(ret.channel.id(), JsValue::from(1));
+    channel_send(ret.channel.id(), JsValue::from(2));
+    assert_eq!(ret.message.get_untracked(), Some(2));
+    assert_eq!(ret.messages.get_untracked(), vec![1, 2]);
+    assert_eq!(ret.index.get_untracked(), Some(1));
+}
+
+#[wasm_bindgen_test]
+async fn channel_index_counts_messages_failing_to_deserialize() {
+    let _owner = setup();
+    let ret = use_channel::<u32>();
+
+    channel_send(ret.channel.id(), JsValue::from(1));
+    channel_send(ret.channel.id(), JsValue::from_str("not a number"));
+    assert_eq!(ret.index.get_untracked(), Some(1));
+    assert_eq!(ret.message.get_untracked(), Some(1));
+    assert!(matches!(ret.error.get_untracked(), Some(UseTauriError::Deserialize(_))));
+
+    channel_send(ret.channel.id(), JsValue::from(3));
+    assert_eq!(ret.index.get_untracked(), Some(2));
+    assert!(ret.messages.get_untracked().is_empty());
+}
+
+#[derive(Clone, Serialize)]
+struct DownloadArgs {
+    on_progress: Channel<u32>,
+}
+
+#[wasm_bindgen_test]
+async fn channel_is_passed_by_id_in_args() {
+    let _owner = setup();
+    let UseChannelReturn { channel, .. } = use_channel::<u32>();
+    let ret = use_invoke_with_args::<DownloadArgs, ()>("download");
+
+    ret.trigger.set(Some(DownloadArgs { on_progress: channel }));
+    settle().await;
+    assert_eq!(call_args("download", 0), Some(format!(r#"{{"on_progress":"__CHANNEL__:{}"}}"#, channel.id())));
+}
+
+#[wasm_bindgen_test]
+async fn channel_detaches_its_handler_on_cleanup() {
+    let owner = setup();
+    let ret = use_channel::<u32>();
+    let id = ret.channel.id();
+
+    owner.cleanup();
+    // would throw calling the dropped closure
+    channel_send(id, JsValue::from(1));
+}
+
+#[wasm_bindgen_test]
+async fn query_invalidated_mid_fetch_fetches_again() {
+    let _owner = setup();
+    let ret = use_query::<(), u32>("list");
+
+    ret.trigger.set(Some(()));
+    settle().await;
+    assert_eq!(call_count("list"), 1);
+
+    invalidate("list");
+    resolve_call("list", 0, JsValue::from(1));
+    settle().await;
+    assert_eq!(call_count("list"), 2);
+    assert_eq!(ret.updated_at.get_untracked(), None);
+    assert!(ret.pending.get_untracked());
+
+    resolve_call("list", 1, JsValue::from(2));
+    settle().await;
+    assert_eq!(ret.data.get_untracked(), Some(2));
+    assert!(ret.updated_at.get_untracked().is_some());
+    assert!(!ret.pending.get_untracked());
+}
+
+#[wasm_bindgen_test]
+async fn query_without_stale_while_revalidate_hides_invalidated_data() {
+    let _owner = setup();
+    let ret = use_query_with_config::<(), u32>(
+        "no_swr",
+        UseQueryConfig::default().ttl(Duration::from_secs(60)).stale_while_revalidate(false),
+    );
+
+    ret.trigger.set(Some(()));
+    settle().await;
+    resolve_call("no_swr", 0, JsValue::from(1));
+    settle().await;
+    assert_eq!(ret.data.get_untracked(), Some(1));
+
+    invalidate("no_swr");
+    settle().await;
+    assert_eq!(ret.data.get_untracked(), None);
+
+    resolve_call("no_swr", 1, JsValue::from(2));
+    settle().await;
+    assert_eq!(ret.data.get_untracked(), Some(2));
+}
+
+#[wasm_bindgen_test]
+async fn query_shares_results_by_key() {
+    let _owner = setup();
+    let first = use_query_with_config::<(), u32>("shared", UseQueryConfig::default().ttl(Duration::from_secs(60)));
+    let second = use_query_with_config::<(), u32>("shared", UseQueryConfig::default().ttl(Duration::from_secs(60)));
+
+    first.trigger.set(Some(()));
+    settle().await;
+    resolve_call("shared", 0, JsValue::from(1));
+    settle().await;
+
+    second.trigger.set(Some(()));
+    settle().await;
+    assert_eq!(call_count("shared"), 1);
+    assert_eq!(second.data.get_untracked(), Some(1));
+}
+
+#[wasm_bindgen_test]
+async fn mutation_rolls_back_when_rejected() {
+    let _owner = setup();
+    let numbers = RwSignal::new(vec![1, 2, 3]);
+    let ret = use_mutation::<u32, ()>(
+        "remove",
+        UseMutationConfig::default().optimistic(numbers, |numbers: &mut Vec<u32>, id: &u32| numbers.retain(|n| n != id)),
+    );
+
+    ret.trigger.set(Some(2));
+    settle().await;
+    assert_eq!(numbers.get_untracked(), vec![1, 3]);
+
+    reject_call("remove", 0, JsValue::from_str("denied"));
+    settle().await;
+    assert_eq!(numbers.get_untracked(), vec![1, 2, 3]);
+}
+
+#[wasm_bindgen_test]
+async fn mutation_keeps_changes_made_after_its_update() {
+    let _owner = setup();
+    let numbers = RwSignal::new(vec![1, 2, 3]);
+    // overlapping calls, queued ones would only update once the previous one settled
+    let ret = use_mutation_with_config::<u32, ()>(
+        "remove_overlapping",
+        UseMutationConfig::default().optimistic(numbers, |numbers: &mut Vec<u32>, id: &u32| numbers.retain(|n| n != id)),
+        UseInvokeConfig::default().policy(InvokePolicy::Parallel),
+    );
+
+    ret.trigger.set(Some(1));
+    settle().await;
+    ret.trigger.set(Some(2));
+    settle().await;
+    assert_eq!(numbers.get_untracked(), vec![3]);
+
+    // rolling back would bring back 2 as well
+    reject_call("remove_overlapping", 0, JsValue::from_str("denied"));
+    settle().await;
+    assert_eq!(numbers.get_untracked(), vec![3]);
+    assert!(matches!(ret.error.get_untracked(), Some(UseTauriError::Command(_, err)) if err == "denied"));
+}
+
+#[wasm_bindgen_test]
+async fn mutation_reports_every_failure() {
+    let _owner = setup();
+    let ret = use_mutation::<u32, ()>("save", UseMutationConfig::default());
+
+    ret.trigger.set(Some(1));
+    settle().await;
+    ret.trigger.set(Some(2));
+    settle().await;
+    assert_eq!(call_count("save"), 1);
+
+    reject_call("save", 0, JsValue::from_str("denied"));
+    settle().await;
+    assert!(matches!(ret.error.get_untracked(), Some(UseTauriError::Command(_, err)) if err == "denied"));
+    assert_eq!(call_count("save"), 2);
+}
+
+#[derive(Clone)]
+struct Unserializable;
+
+impl Serialize for Unserializable {
+    fn serialize<S: Serializer>(&self, _: S) -> Result<S::Ok, S::Error> {
+        Err(serde::ser::Error::custom("unserializable"))
+    }
+}
+
+#[wasm_bindgen_test]
+async fn mutation_rolls_back_when_args_fail_to_serialize() {
+    let _owner = setup();
+    let numbers = RwSignal::new(vec![1, 2, 3]);
+    let ret = use_mutation::<Unserializable, ()>(
+        "unserializable",
+        UseMutationConfig::default().optimistic(numbers, |numbers: &mut Vec<u32>, _| numbers.clear()),
+    );
+
+    ret.trigger.set(Some(Unserializable));
+    settle().await;
+    assert_eq!(call_count("unserializable"), 0);
+    assert_eq!(numbers.get_untracked(), vec![1, 2, 3]);
+    assert!(matches!(ret.error.get_untracked(), Some(UseTauriError::Serialize(_))));
+}
+
+#[wasm_bindgen_test]
+async fn mutation_invalidates_queries() {
+    let _owner = setup();
+    let query = use_query_with_config::<(), u32>("items", UseQueryConfig::default().ttl(Duration::from_secs(60)));
+    let mutation = use_mutation::<u32, ()>("add_item", UseMutationConfig::default().invalidates("items"));
+
+    query.trigger.set(Some(()));
+    settle().await;
+    resolve_call("items", 0, JsValue::from(1));
+    settle().await;
+
+    mutation.trigger.set(Some(2));
+    settle().await;
+    resolve_call("add_item", 0, JsValue::UNDEFINED);
+    settle().await;
+    assert_eq!(call_count("items"), 2);
+
+    resolve_call("items", 1, JsValue::from(2));
+    settle().await;
+    assert_eq!(query.data.get_untracked(), Some(2));
+}
+
+#[wasm_bindgen_test]
+async fn mutation_invalidates_queries_after_the_owner_is_cleaned_up() {
+    let _owner = setup();
+    let query = use_query_with_config::<(), u32>("entries", UseQueryConfig::default().ttl(Duration::from_secs(60)));
+
+    query.trigger.set(Some(()));
+    settle().await;
+    resolve_call("entries", 0, JsValue::from(1));
+    settle().await;
+
+    // e.g. a dialog closing as soon as it saved
+    let form = Owner::new();
+    let mutation = form.with(|| use_mutation::<u32, ()>("add_entry", UseMutationConfig::default().invalidates("entries")));
+    mutation.trigger.set(Some(2));
+    settle().await;
+    form.cleanup();
+
+    resolve_call("add_entry", 0, JsValue::UNDEFINED);
+    settle().await;
+    assert_eq!(call_count("entries"), 2);
+}
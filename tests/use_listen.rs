@@ -0,0 +1,491 @@
+#![cfg(target_arch = "wasm32")]
+// run with `cargo test --target wasm32-unknown-unknown` and `wasm-bindgen-test-runner` as the runner
+
+use std::{future::poll_fn, pin::Pin};
+use any_spawner::Executor;
+use futures_core::Stream;
+use reactive_graph::{
+    owner::Owner,
+    signal::RwSignal,
+    traits::{GetUntracked as _, Set as _},
+};
+use tauri_use::*;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+// stands in for `window.__TAURI__.event`, `once` handlers deactivate after their first event
+#[wasm_bindgen(inline_js = r#"
+const state = { handlers: [], listen: 0, once: 0, unlisten: 0, nextId: 0, lastTarget: null, emitted: [], emitError: undefined, listenError: undefined };
+
+export function install_mock() {
+    Object.assign(state, { handlers: [], listen: 0, once: 0, unlisten: 0, nextId: 0, lastTarget: null, emitted: [], emitError: undefined, listenError: undefined });
+
+    const register = (once) => async (event, handler, options) => {
+        if (state.listenError !== undefined) throw state.listenError;
+        once ? state.once++ : state.listen++;
+        state.lastTarget = JSON.stringify(options);
+
+        const entry = { event, handler, once, active: true };
+        state.handlers.push(entry);
+        return () => {
+            if (entry.active) {
+                entry.active = false;
+                state.unlisten++;
+            }
+        };
+    };
+
+    const record = async (target, event, payload) => {
+        if (state.emitError !== undefined) throw state.emitError;
+        state.emitted.push(JSON.stringify({ target, event, payload }));
+    };
+
+    globalThis.window = globalThis;
+    globalThis.__TAURI__ = {
+        event: {
+            listen: register(false),
+            once: register(true),
+            emit: (event, payload) => record(null, event, payload),
+            emitTo: (target, event, payload) => record(target, event, payload),
+        },
+    };
+}
+
+export function emit_mock(event, payload) {
+    for (const entry of state.handlers) {
+        if (entry.active && entry.event === event) {
+            if (entry.once) entry.active = false;
+            entry.handler({ event, id: state.nextId++, payload });
+        }
+    }
+}
+
+export function active_handlers(event) {
+    return state.handlers.filter((entry) => entry.active && entry.event === event).length;
+}
+
+// handlers whose closure was dropped throw when called
+export function dropped_handlers() {
+    return state.handlers.filter((entry) => {
+        if (entry.active) return false;
+        try {
+            entry.handler({ event: entry.event, id: 0, payload: 0 });
+            return false;
+        } catch {
+            return true;
+        }
+    }).length;
+}
+
+export function emitted() { return state.emitted.slice(); }
+export function reject_emits(err) { state.emitError = err; }
+export function reject_listens(err) { state.listenError = err; }
+export function listen_calls() { return state.listen; }
+export function once_calls() { return state.once; }
+export function unlisten_calls() { return state.unlisten; }
+export function last_target() { return state.lastTarget; }
+export function tick() { return new Promise((resolve) => setTimeout(resolve, 0)); }
+"#)]
+extern "C" {
+    fn install_mock();
+    fn emit_mock(event: &str, payload: JsValue);
+    fn active_handlers(event: &str) -> u32;
+    fn dropped_handlers() -> u32;
+    fn emitted() -> Vec<String>;
+    fn reject_emits(err: JsValue);
+    fn reject_listens(err: JsValue);
+    fn listen_calls() -> u32;
+    fn once_calls() -> u32;
+    fn unlisten_calls() -> u32;
+    fn last_target() -> Option<String>;
+    fn tick() -> js_sys::Promise;
+}
+
+const EVENT: &str = "test://status";
+const TARGET: EventTarget = EventTarget::Window("main");
+
+fn setup() -> Owner {
+    let _ = Executor::init_wasm_bindgen();
+    install_mock();
+
+    let owner = Owner::new();
+    owner.set();
+    owner
+}
+
+/// Lets the pending `listen`/`once` promises resolve
+async fn settle() {
+    JsFuture::from(tick()).await.unwrap();
+}
+
+fn emit(value: u32) {
+    emit_mock(EVENT, JsValue::from(value));
+}
+
+async fn assert_persistent<O, C>(ret: UseListenReturn<u32, O, C>)
+where
+    O: Fn() + Clone + Send + Sync + 'static,
+    C: Fn() + Clone + Send + Sync + 'static,
+{
+    (ret.open)();
+    settle().await;
+
+    emit(1);
+    emit(2);
+    assert_eq!(ret.data.get_untracked(), Some(2));
+    assert_eq!(ret.event_id.get_untracked(), Some(1));
+    assert_eq!(active_handlers(EVENT), 1);
+    assert_eq!((listen_calls(), once_calls()), (1, 0));
+}
+
+async fn assert_once<O, C>(ret: UseListenReturn<u32, O, C>)
+where
+    O: Fn() + Clone + Send + Sync + 'static,
+    C: Fn() + Clone + Send + Sync + 'static,
+{
+    (ret.open)();
+    settle().await;
+
+    emit(1);
+    emit(2);
+    assert_eq!(ret.data.get_untracked(), Some(1));
+    assert_eq!(ret.event_id.get_untracked(), Some(0));
+    assert_eq!(active_handlers(EVENT), 0);
+    assert_eq!((listen_calls(), once_calls()), (0, 1));
+}
+
+async fn assert_reentrant<O, C>(ret: UseListenReturn<u32, O, C>)
+where
+    O: Fn() + Clone + Send + Sync + 'static,
+    C: Fn() + Clone + Send + Sync + 'static,
+{
+    // closing before anything is open does nothing
+    (ret.close)();
+    settle().await;
+    assert_eq!(active_handlers(EVENT), 0);
+
+    // reopening while the first subscription is pending keeps only the latest one
+    (ret.open)();
+    (ret.open)();
+    settle().await;
+    assert_eq!(active_handlers(EVENT), 1);
+    assert_eq!(unlisten_calls(), 1);
+
+    (ret.close)();
+    (ret.close)();
+    assert_eq!(active_handlers(EVENT), 0);
+    assert_eq!(unlisten_calls(), 2);
+
+    // closing while a subscription is pending drops it once it resolves
+    (ret.open)();
+    (ret.close)();
+    settle().await;
+    assert_eq!(active_handlers(EVENT), 0);
+
+    // reopening after a close subscribes again
+    (ret.open)();
+    settle().await;
+    assert_eq!(active_handlers(EVENT), 1);
+
+    emit(7);
+    assert_eq!(ret.data.get_untracked(), Some(7));
+}
+
+async fn assert_cleanup<O, C>(owner: Owner, ret: UseListenReturn<u32, O, C>)
+where
+    O: Fn() + Clone + Send + Sync + 'static,
+    C: Fn() + Clone + Send + Sync + 'static,
+{
+    (ret.open)();
+    settle().await;
+    assert_eq!(active_handlers(EVENT), 1);
+
+    owner.cleanup();
+    assert_eq!(active_handlers(EVENT), 0);
+    assert_eq!(unlisten_calls(), 1);
+}
+
+async fn assert_cleanup_while_pending<O, C>(owner: Owner, ret: UseListenReturn<u32, O, C>)
+where
+    O: Fn() + Clone + Send + Sync + 'static,
+    C: Fn() + Clone + Send + Sync + 'static,
+{
+    (ret.open)();
+    owner.cleanup();
+    settle().await;
+    assert_eq!(active_handlers(EVENT), 0);
+}
+
+#[wasm_bindgen_test]
+async fn use_listen_keeps_listening() {
+    let _owner = setup();
+    assert_persistent(use_listen::<u32>(EventType::custom(EVENT))).await;
+    assert_eq!(last_target().as_deref(), Some(r#"{"kind":"Any"}"#));
+}
+
+#[wasm_bindgen_test]
+async fn use_listen_with_target_keeps_listening() {
+    let _owner = setup();
+    assert_persistent(use_listen_with_target::<u32>(EventType::custom(EVENT), TARGET)).await;
+    assert_eq!(last_target().as_deref(), Some(r#"{"kind":"Window","label":"main"}"#));
+}
+
+#[wasm_bindgen_test]
+async fn use_once_listen_stops_after_first_event() {
+    let _owner = setup();
+    assert_once(use_once_listen::<u32>(EventType::custom(EVENT))).await;
+    assert_eq!(last_target().as_deref(), Some(r#"{"kind":"Any"}"#));
+}
+
+#[wasm_bindgen_test]
+async fn use_once_listen_with_target_stops_after_first_event() {
+    let _owner = setup();
+    assert_once(use_once_listen_with_target::<u32>(EventType::custom(EVENT), TARGET)).await;
+    assert_eq!(last_target().as_deref(), Some(r#"{"kind":"Window","label":"main"}"#));
+}
+
+#[wasm_bindgen_test]
+async fn use_listen_open_close_reentrant() {
+    let _owner = setup();
+    assert_reentrant(use_listen::<u32>(EventType::custom(EVENT))).await;
+}
+
+#[wasm_bindgen_test]
+async fn use_listen_with_target_open_close_reentrant() {
+    let _owner = setup();
+    assert_reentrant(use_listen_with_target::<u32>(EventType::custom(EVENT), TARGET)).await;
+}
+
+#[wasm_bindgen_test]
+async fn use_once_listen_open_close_reentrant() {
+    let _owner = setup();
+    assert_reentrant(use_once_listen::<u32>(EventType::custom(EVENT))).await;
+}
+
+#[wasm_bindgen_test]
+async fn use_once_listen_with_target_open_close_reentrant() {
+    let _owner = setup();
+    assert_reentrant(use_once_listen_with_target::<u32>(EventType::custom(EVENT), TARGET)).await;
+}
+
+#[wasm_bindgen_test]
+async fn use_listen_unlistens_on_cleanup() {
+    let owner = setup();
+    assert_cleanup(owner.clone(), use_listen::<u32>(EventType::custom(EVENT))).await;
+
+    let owner = setup();
+    assert_cleanup_while_pending(owner.clone(), use_listen::<u32>(EventType::custom(EVENT))).await;
+}
+
+#[wasm_bindgen_test]
+async fn use_listen_with_target_unlistens_on_cleanup() {
+    let owner = setup();
+    assert_cleanup(owner.clone(), use_listen_with_target::<u32>(EventType::custom(EVENT), TARGET)).await;
+
+    let owner = setup();
+    assert_cleanup_while_pending(owner.clone(), use_listen_with_target::<u32>(EventType::custom(EVENT), TARGET))
+        .await;
+}
+
+#[wasm_bindgen_test]
+async fn use_once_listen_unlistens_on_cleanup() {
+    let owner = setup();
+    assert_cleanup(owner.clone(), use_once_listen::<u32>(EventType::custom(EVENT))).await;
+
+    let owner = setup();
+    assert_cleanup_while_pending(owner.clone(), use_once_listen::<u32>(EventType::custom(EVENT))).await;
+}
+
+#[wasm_bindgen_test]
+async fn use_once_listen_with_target_unlistens_on_cleanup() {
+    let owner = setup();
+    assert_cleanup(owner.clone(), use_once_listen_with_target::<u32>(EventType::custom(EVENT), TARGET)).await;
+
+    let owner = setup();
+    assert_cleanup_while_pending(
+        owner.clone(),
+        use_once_listen_with_target::<u32>(EventType::custom(EVENT), TARGET),
+    )
+    .await;
+}
+
+#[wasm_bindgen_test]
+async fn use_listen_buffered_keeps_bursts() {
+    let _owner = setup();
+    let ret = use_listen_buffered::<u32>(EventType::custom(EVENT), UseListenBufferedConfig::default().capacity(3));
+    (ret.open)();
+    settle().await;
+
+    for value in 1..=5 {
+        emit(value);
+    }
+    assert_eq!(Vec::from(ret.buffer.get_untracked()), vec![3, 4, 5]);
+    assert_eq!((ret.drain)(), vec![3, 4, 5]);
+    assert!(ret.buffer.get_untracked().is_empty());
+
+    emit(6);
+    assert_eq!((ret.drain)(), vec![6]);
+}
+
+#[wasm_bindgen_test]
+async fn use_listen_buffered_drops_newest() {
+    let _owner = setup();
+    let ret = use_listen_buffered::<u32>(
+        EventType::custom(EVENT),
+        UseListenBufferedConfig::default()
+            .capacity(2)
+            .overflow(BufferOverflow::DropNewest)
+            .target(TARGET),
+    );
+    (ret.open)();
+    settle().await;
+
+    for value in 1..=5 {
+        emit(value);
+    }
+    assert_eq!((ret.drain)(), vec![1, 2]);
+    assert_eq!(last_target().as_deref(), Some(r#"{"kind":"Window","label":"main"}"#));
+}
+
+async fn next<T>(stream: &mut EventStream<T>) -> Option<Result<Event<T>, UseListenError>> {
+    poll_fn(|cx| Pin::new(&mut *stream).poll_next(cx)).await
+}
+
+#[wasm_bindgen_test]
+async fn listen_stream_yields_every_event() {
+    let _owner = setup();
+    let mut events = listen_stream::<u32>(EventType::custom(EVENT), TARGET);
+    settle().await;
+    assert_eq!(last_target().as_deref(), Some(r#"{"kind":"Window","label":"main"}"#));
+
+    emit(1);
+    emit(2);
+    emit_mock(EVENT, JsValue::from("not a number"));
+    assert_eq!(next(&mut events).await.unwrap().unwrap().payload, 1);
+    assert_eq!(next(&mut events).await.unwrap().unwrap().payload, 2);
+    assert!(matches!(next(&mut events).await, Some(Err(UseListenError::Deserialize(_)))));
+
+    drop(events);
+    assert_eq!(active_handlers(EVENT), 0);
+    assert_eq!(unlisten_calls(), 1);
+}
+
+#[wasm_bindgen_test]
+async fn listen_stream_dropped_while_pending() {
+    let _owner = setup();
+    drop(listen_stream::<u32>(EventType::custom(EVENT), EventTarget::Any));
+    settle().await;
+    assert_eq!(listen_calls(), 1);
+    assert_eq!(active_handlers(EVENT), 0);
+}
+
+#[wasm_bindgen_test]
+async fn use_listen_resubscribes_when_event_changes() {
+    let _owner = setup();
+    let event = RwSignal::new(EventType::custom("job://1/progress"));
+    let ret = use_listen::<u32>(event);
+
+    // nothing is listened to before `open`
+    event.set(EventType::custom(format!("job://{}/progress", 2)));
+    settle().await;
+    assert_eq!(listen_calls(), 0);
+
+    (ret.open)();
+    settle().await;
+    assert_eq!(active_handlers("job://2/progress"), 1);
+
+    event.set(EventType::custom(format!("job://{}/progress", 3)));
+    settle().await;
+    assert_eq!(active_handlers("job://2/progress"), 0);
+    assert_eq!(active_handlers("job://3/progress"), 1);
+
+    emit_mock("job://3/progress", JsValue::from(42));
+    assert_eq!(ret.data.get_untracked(), Some(42));
+    // the handler of the previous name is released along with its subscription
+    assert_eq!(dropped_handlers(), 1);
+
+    // a closed listener stays closed
+    (ret.close)();
+    event.set(EventType::custom("job://4/progress"));
+    settle().await;
+    assert_eq!(active_handlers("job://4/progress"), 0);
+    assert_eq!(dropped_handlers(), 2);
+}
+
+#[wasm_bindgen_test]
+async fn invalid_event_name_is_reported() {
+    let _owner = setup();
+    let invalid = || EventType::custom(format!("job://{}/progress", "a.b"));
+
+    let ret = use_listen::<u32>(invalid());
+    (ret.open)();
+    settle().await;
+    assert!(matches!(ret.error.get_untracked(), Some(UseListenError::Event(name, _)) if name == "job://a.b/progress"));
+    assert_eq!(listen_calls(), 0);
+
+    let mut events = listen_stream::<u32>(invalid(), EventTarget::Any);
+    assert!(matches!(next(&mut events).await, Some(Err(UseListenError::Event(..)))));
+    assert!(next(&mut events).await.is_none());
+
+    assert!(matches!(tauri_use::emit(invalid(), 1).await, Err(UseListenError::Event(..))));
+}
+
+#[wasm_bindgen_test]
+async fn emit_and_emit_to_send_the_payload() {
+    let _owner = setup();
+    tauri_use::emit(EventType::custom(EVENT), 1).await.unwrap();
+    emit_to(TARGET, EventType::custom(EVENT), 2).await.unwrap();
+
+    assert_eq!(emitted(), vec![
+        r#"{"target":null,"event":"test://status","payload":1}"#.to_string(),
+        r#"{"target":{"kind":"Window","label":"main"},"event":"test://status","payload":2}"#.to_string(),
+    ]);
+}
+
+#[wasm_bindgen_test]
+async fn emit_rejections_keep_their_message() {
+    let _owner = setup();
+
+    reject_emits(js_sys::Error::new("ipc failed").into());
+    let err = tauri_use::emit(EventType::custom(EVENT), 1).await.unwrap_err();
+    assert!(matches!(err, UseListenError::Event(_, message) if message == "ipc failed"));
+
+    reject_emits(js_sys::JSON::parse(r#"{"code":403}"#).unwrap());
+    let err = emit_to(TARGET, EventType::custom(EVENT), 1).await.unwrap_err();
+    assert!(matches!(err, UseListenError::Event(_, message) if message == r#"{"code":403}"#));
+}
+
+#[wasm_bindgen_test]
+async fn use_emit_reports_pending_and_errors() {
+    let _owner = setup();
+    let ret = use_emit::<u32>(EventType::custom(EVENT));
+    let targeted = use_emit_with_target::<u32>(EventType::custom(EVENT), TARGET);
+
+    ret.trigger.set(Some(1));
+    targeted.trigger.set(Some(2));
+    settle().await;
+    assert_eq!(emitted().len(), 2);
+    assert!(!ret.pending.get_untracked());
+    assert!(ret.error.get_untracked().is_none());
+
+    reject_emits(JsValue::from_str("denied"));
+    ret.trigger.set(Some(3));
+    settle().await;
+    assert!(matches!(ret.error.get_untracked(), Some(UseListenError::Event(_, message)) if message == "denied"));
+}
+
+#[wasm_bindgen_test]
+async fn listen_rejections_keep_their_message() {
+    let _owner = setup();
+
+    reject_listens(js_sys::Error::new("ipc failed").into());
+    let ret = use_listen::<u32>(EventType::custom(EVENT));
+    (ret.open)();
+    settle().await;
+    assert!(matches!(ret.error.get_untracked(), Some(UseListenError::Event(_, message)) if message == "ipc failed"));
+
+    reject_listens(js_sys::JSON::parse(r#"{"code":403}"#).unwrap());
+    let mut events = listen_stream::<u32>(EventType::custom(EVENT), EventTarget::Any);
+    assert!(matches!(next(&mut events).await, Some(Err(UseListenError::Event(_, message))) if message == r#"{"code":403}"#));
+}